@@ -0,0 +1,243 @@
+//! This crate provides the `#[serde_bytes::auto]` attribute macro. It is not
+//! intended to be used directly; enable the `derive` feature of serde_bytes
+//! instead.
+
+#![allow(
+    clippy::doc_markdown,
+    clippy::match_same_arms,
+    clippy::needless_pass_by_value
+)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Field, Fields,
+    GenericArgument, Meta, PathArguments, Type,
+};
+
+#[proc_macro_attribute]
+pub fn auto(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = TokenStream2::from(args);
+    let mut input = parse_macro_input!(input as DeriveInput);
+
+    let result = if args.is_empty() {
+        expand(&mut input)
+    } else {
+        Err(Error::new_spanned(
+            args,
+            "#[serde_bytes::auto] does not take arguments",
+        ))
+    };
+
+    match result {
+        Ok(()) => quote!(#input).into(),
+        Err(err) => {
+            let err = err.to_compile_error();
+            quote!(#err #input).into()
+        }
+    }
+}
+
+fn expand(input: &mut DeriveInput) -> Result<(), Error> {
+    match &mut input.data {
+        Data::Struct(data) => expand_fields(&mut data.fields),
+        Data::Enum(data) => {
+            for variant in &mut data.variants {
+                expand_fields(&mut variant.fields)?;
+            }
+            Ok(())
+        }
+        Data::Union(data) => Err(Error::new(
+            data.union_token.span,
+            "#[serde_bytes::auto] does not support unions",
+        )),
+    }
+}
+
+fn expand_fields(fields: &mut Fields) -> Result<(), Error> {
+    for field in fields {
+        expand_field(field)?;
+    }
+    Ok(())
+}
+
+fn expand_field(field: &mut Field) -> Result<(), Error> {
+    let mut ignore = false;
+    let mut errors = None::<Error>;
+    field.attrs.retain(|attr| {
+        if !attr.path().is_ident("serde_bytes") {
+            return true;
+        }
+        match parse_ignore(attr) {
+            Ok(()) => ignore = true,
+            Err(err) => match &mut errors {
+                Some(errors) => errors.combine(err),
+                None => errors = Some(err),
+            },
+        }
+        false
+    });
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    if ignore
+        || has_serde_attr(
+            &field.attrs,
+            &["with", "serialize_with", "deserialize_with"],
+        )
+    {
+        return Ok(());
+    }
+    if !is_bytes(&field.ty) {
+        return Ok(());
+    }
+    // Serde only requires 'de: 'a for a Cow field if it is marked borrow, and
+    // serde_bytes' Cow impl needs that bound.
+    if is_borrowed_cow(&field.ty) && !has_serde_attr(&field.attrs, &["borrow"]) {
+        field
+            .attrs
+            .push(parse_quote!(#[serde(with = "serde_bytes", borrow)]));
+    } else {
+        field
+            .attrs
+            .push(parse_quote!(#[serde(with = "serde_bytes")]));
+    }
+    Ok(())
+}
+
+fn parse_ignore(attr: &Attribute) -> Result<(), Error> {
+    let mut ignore = false;
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("ignore") {
+            ignore = true;
+            Ok(())
+        } else {
+            Err(meta.error("unrecognized serde_bytes attribute"))
+        }
+    })?;
+    if ignore {
+        Ok(())
+    } else {
+        Err(Error::new_spanned(attr, "expected #[serde_bytes(ignore)]"))
+    }
+}
+
+// Whether any #[serde(...)] attribute contains one of `names`, such as a
+// field that already names its own (de)serialization function.
+fn has_serde_attr(attrs: &[Attribute], names: &[&str]) -> bool {
+    attrs.iter().any(|attr| {
+        if !attr.path().is_ident("serde") {
+            return false;
+        }
+        let Meta::List(list) = &attr.meta else {
+            return false;
+        };
+        let mut found = false;
+        let _ = list.parse_nested_meta(|meta| {
+            if names.iter().any(|name| meta.path.is_ident(name)) {
+                found = true;
+            }
+            if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|_| Ok(()))?;
+            }
+            Ok(())
+        });
+        found
+    })
+}
+
+// Vec<u8>, [u8; N], [u8], &[u8], &[u8; N], Box<[u8]>, Cow<[u8]>, and Option
+// of any of these.
+fn is_bytes(ty: &Type) -> bool {
+    match ty {
+        Type::Array(ty) => is_u8(&ty.elem),
+        Type::Slice(ty) => is_u8(&ty.elem),
+        Type::Reference(ty) => ty.mutability.is_none() && is_u8_slice_or_array(&ty.elem),
+        Type::Group(ty) => is_bytes(&ty.elem),
+        Type::Paren(ty) => is_bytes(&ty.elem),
+        Type::Path(ty) if ty.qself.is_none() => {
+            let Some(segment) = ty.path.segments.last() else {
+                return false;
+            };
+            let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return false;
+            };
+            let mut types = args.args.iter().filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            });
+            let (Some(arg), None) = (types.next(), types.next()) else {
+                return false;
+            };
+            if segment.ident == "Vec" {
+                is_u8(arg)
+            } else if segment.ident == "Box" || segment.ident == "Cow" {
+                is_u8_slice(arg)
+            } else if segment.ident == "Option" {
+                is_bytes(arg)
+            } else {
+                false
+            }
+        }
+        _ => false,
+    }
+}
+
+// Cow<'a, [u8]> or Option of it, with a lifetime other than 'static.
+fn is_borrowed_cow(ty: &Type) -> bool {
+    match ty {
+        Type::Group(ty) => is_borrowed_cow(&ty.elem),
+        Type::Paren(ty) => is_borrowed_cow(&ty.elem),
+        Type::Path(ty) if ty.qself.is_none() => {
+            let Some(segment) = ty.path.segments.last() else {
+                return false;
+            };
+            let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return false;
+            };
+            if segment.ident == "Cow" {
+                args.args.iter().any(|arg| match arg {
+                    GenericArgument::Lifetime(lifetime) => lifetime.ident != "static",
+                    _ => false,
+                })
+            } else if segment.ident == "Option" {
+                args.args.iter().any(|arg| match arg {
+                    GenericArgument::Type(ty) => is_borrowed_cow(ty),
+                    _ => false,
+                })
+            } else {
+                false
+            }
+        }
+        _ => false,
+    }
+}
+
+fn is_u8_slice_or_array(ty: &Type) -> bool {
+    match ty {
+        Type::Array(ty) => is_u8(&ty.elem),
+        _ => is_u8_slice(ty),
+    }
+}
+
+fn is_u8_slice(ty: &Type) -> bool {
+    match ty {
+        Type::Slice(ty) => is_u8(&ty.elem),
+        Type::Group(ty) => is_u8_slice(&ty.elem),
+        Type::Paren(ty) => is_u8_slice(&ty.elem),
+        _ => false,
+    }
+}
+
+fn is_u8(ty: &Type) -> bool {
+    match ty {
+        Type::Path(ty) => ty.qself.is_none() && ty.path.is_ident("u8"),
+        Type::Group(ty) => is_u8(&ty.elem),
+        Type::Paren(ty) => is_u8(&ty.elem),
+        _ => false,
+    }
+}
@@ -0,0 +1,159 @@
+#![allow(clippy::derive_partial_eq_without_eq, clippy::ref_option_ref)]
+
+use serde_bytes::ByteBuf;
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_tokens, Token};
+use std::borrow::Cow;
+
+#[serde_bytes::auto]
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Test<'a> {
+    vec: Vec<u8>,
+    array: [u8; 2],
+    slice: &'a [u8],
+    borrowed_array: &'a [u8; 2],
+    boxed_slice: Box<[u8]>,
+    cow_slice: Cow<'a, [u8]>,
+    opt_vec: Option<Vec<u8>>,
+    opt_slice: Option<&'a [u8]>,
+    #[serde_bytes(ignore)]
+    ignored: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    explicit: Vec<u8>,
+    byte_buf: ByteBuf,
+    other: Vec<u16>,
+}
+
+#[serde_bytes::auto]
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct CowOnly<'a> {
+    cow: Cow<'a, [u8]>,
+    opt_cow: Option<Cow<'a, [u8]>>,
+    #[serde(borrow)]
+    explicit_borrow: Cow<'a, [u8]>,
+}
+
+#[serde_bytes::auto]
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+enum Enum {
+    Tuple(Vec<u8>, u8),
+    Struct { bytes: Vec<u8> },
+}
+
+#[test]
+fn test_struct() {
+    let test = Test {
+        vec: b"vec".to_vec(),
+        array: [1, 2],
+        slice: b"slice",
+        borrowed_array: &[3, 4],
+        boxed_slice: b"box".to_vec().into_boxed_slice(),
+        cow_slice: Cow::Borrowed(b"cow"),
+        opt_vec: Some(b"opt".to_vec()),
+        opt_slice: None,
+        ignored: vec![5],
+        explicit: b"explicit".to_vec(),
+        byte_buf: ByteBuf::from(b"buf".to_vec()),
+        other: vec![6],
+    };
+
+    assert_tokens(
+        &test,
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 12,
+            },
+            Token::Str("vec"),
+            Token::Bytes(b"vec"),
+            Token::Str("array"),
+            Token::Bytes(&[1, 2]),
+            Token::Str("slice"),
+            Token::BorrowedBytes(b"slice"),
+            Token::Str("borrowed_array"),
+            Token::BorrowedBytes(&[3, 4]),
+            Token::Str("boxed_slice"),
+            Token::Bytes(b"box"),
+            Token::Str("cow_slice"),
+            Token::BorrowedBytes(b"cow"),
+            Token::Str("opt_vec"),
+            Token::Some,
+            Token::Bytes(b"opt"),
+            Token::Str("opt_slice"),
+            Token::None,
+            Token::Str("ignored"),
+            Token::Seq { len: Some(1) },
+            Token::U8(5),
+            Token::SeqEnd,
+            Token::Str("explicit"),
+            Token::Bytes(b"explicit"),
+            Token::Str("byte_buf"),
+            Token::Bytes(b"buf"),
+            Token::Str("other"),
+            Token::Seq { len: Some(1) },
+            Token::U16(6),
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_cow_only() {
+    let test = CowOnly {
+        cow: Cow::Borrowed(b"cow"),
+        opt_cow: Some(Cow::Borrowed(b"opt")),
+        explicit_borrow: Cow::Borrowed(b"explicit"),
+    };
+
+    assert_tokens(
+        &test,
+        &[
+            Token::Struct {
+                name: "CowOnly",
+                len: 3,
+            },
+            Token::Str("cow"),
+            Token::BorrowedBytes(b"cow"),
+            Token::Str("opt_cow"),
+            Token::Some,
+            Token::BorrowedBytes(b"opt"),
+            Token::Str("explicit_borrow"),
+            Token::BorrowedBytes(b"explicit"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_enum() {
+    assert_tokens(
+        &Enum::Tuple(b"tuple".to_vec(), 1),
+        &[
+            Token::TupleVariant {
+                name: "Enum",
+                variant: "Tuple",
+                len: 2,
+            },
+            Token::Bytes(b"tuple"),
+            Token::U8(1),
+            Token::TupleVariantEnd,
+        ],
+    );
+
+    assert_tokens(
+        &Enum::Struct {
+            bytes: b"struct".to_vec(),
+        },
+        &[
+            Token::StructVariant {
+                name: "Enum",
+                variant: "Struct",
+                len: 1,
+            },
+            Token::Str("bytes"),
+            Token::Bytes(b"struct"),
+            Token::StructVariantEnd,
+        ],
+    );
+}
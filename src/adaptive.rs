@@ -0,0 +1,499 @@
+//! Bytes as a native byte string in binary formats and as text in
+//! human-readable ones.
+//!
+//! `#[serde(with = "serde_bytes")]` always writes a byte string, which
+//! human-readable formats like JSON, YAML or TOML turn into an array of
+//! numbers. [`Adaptive<R>`] asks the format whether it is human-readable and
+//! uses representation `R` only if it is.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//! use serde_bytes::adaptive::{Adaptive, Base64, Hex};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Key {
+//!     #[serde(with = "Adaptive::<Hex>")]
+//!     id: [u8; 4],
+//!
+//!     #[serde(with = "Adaptive::<Base64>")]
+//!     secret: Vec<u8>,
+//! }
+//!
+//! let key = Key {
+//!     id: [0xde, 0xad, 0xbe, 0xef],
+//!     secret: b"hunter2".to_vec(),
+//! };
+//!
+//! let json = serde_json::to_string(&key).unwrap();
+//! assert_eq!(json, r#"{"id":"deadbeef","secret":"aHVudGVyMg=="}"#);
+//! ```
+
+use crate::de::Deserialize;
+use crate::repr;
+use crate::ser::Serialize;
+use core::fmt;
+use core::marker::PhantomData;
+use core::str;
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use serde::ser::{self, SerializeSeq, Serializer};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+pub use crate::decode::{DecodeError, DecodeErrorKind};
+pub use crate::repr::Representation;
+
+/// Representation `R` in human-readable formats, a native byte string
+/// otherwise.
+///
+/// Use it as `#[serde(with = "Adaptive::<R>")]`.
+pub struct Adaptive<R> {
+    repr: PhantomData<R>,
+}
+
+impl<R> Adaptive<R>
+where
+    R: Representation,
+{
+    /// Serde `serialize_with` function.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        repr::serialize::<Self, T, S>(bytes, serializer)
+    }
+
+    /// Serde `deserialize_with` function.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        repr::deserialize::<Self, T, D>(deserializer)
+    }
+}
+
+impl<R> Representation for Adaptive<R>
+where
+    R: Representation,
+{
+    fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            R::serialize(bytes, serializer)
+        } else {
+            serializer.serialize_bytes(bytes)
+        }
+    }
+
+    fn deserialize<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Visitor<'de>,
+    {
+        if deserializer.is_human_readable() {
+            R::deserialize(deserializer, visitor)
+        } else {
+            deserializer.deserialize_bytes(visitor)
+        }
+    }
+}
+
+/// Text encoding of bytes, usable as a [`Representation`] that writes a
+/// string.
+pub trait Encoding {
+    /// Name of the encoding for error messages, such as `"hex"`.
+    const NAME: &'static str;
+
+    /// Tag that may precede the text to name the encoding, such as `"0x"`
+    /// for hex, or `""` if the encoding has none.
+    const PREFIX: &'static str = "";
+
+    /// Encode bytes to text.
+    fn encode(bytes: &[u8]) -> String;
+
+    /// Decode text to bytes, or report where the text is not valid in this
+    /// encoding.
+    fn decode(text: &str) -> Result<Vec<u8>, DecodeError>;
+}
+
+impl<E> Representation for E
+where
+    E: Encoding,
+{
+    fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&E::encode(bytes))
+    }
+
+    fn deserialize<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Visitor<'de>,
+    {
+        deserializer.deserialize_str(EncodingVisitor::<E, V> {
+            visitor,
+            encoding: PhantomData,
+        })
+    }
+}
+
+struct EncodingVisitor<E, V> {
+    visitor: V,
+    encoding: PhantomData<E>,
+}
+
+impl<'de, E, V> Visitor<'de> for EncodingVisitor<E, V>
+where
+    E: Encoding,
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a {} string", E::NAME)
+    }
+
+    fn visit_str<Err>(self, v: &str) -> Result<V::Value, Err>
+    where
+        Err: de::Error,
+    {
+        match E::decode(v) {
+            Ok(bytes) => self.visitor.visit_byte_buf(bytes),
+            Err(err) => Err(err.into_de_error(E::NAME, v)),
+        }
+    }
+}
+
+/// Encoding `E` with its [prefix](Encoding::PREFIX), which is written when
+/// serializing and required when deserializing.
+///
+/// ```
+/// # use serde_derive::{Deserialize, Serialize};
+/// use serde::{Deserialize, Serialize};
+/// use serde_bytes::adaptive::{Adaptive, Hex, Prefixed};
+/// use serde_bytes::ByteArray;
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Account {
+///     #[serde(with = "Adaptive::<Prefixed<Hex>>")]
+///     address: ByteArray<4>,
+/// }
+///
+/// let json = r#"{"address":"0x0badcafe"}"#;
+/// let account: Account = serde_json::from_str(json).unwrap();
+/// assert_eq!(serde_json::to_string(&account).unwrap(), json);
+///
+/// let json = r#"{"address":"0badcafe"}"#;
+/// let err = serde_json::from_str::<Account>(json).err().unwrap();
+/// assert_eq!(
+///     err.to_string(),
+///     "invalid value: string \"0badcafe\", expected a hex string starting with `0x` at line 1 column 21",
+/// );
+/// ```
+pub struct Prefixed<E> {
+    encoding: PhantomData<E>,
+}
+
+/// Encoding `E` with its [prefix](Encoding::PREFIX), which is written when
+/// serializing and may be left out when deserializing.
+pub struct OptionallyPrefixed<E> {
+    encoding: PhantomData<E>,
+}
+
+impl<E> Representation for Prefixed<E>
+where
+    E: Encoding,
+{
+    fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_prefixed::<E, S>(bytes, serializer)
+    }
+
+    fn deserialize<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Visitor<'de>,
+    {
+        deserializer.deserialize_str(PrefixedVisitor::<E, V> {
+            visitor,
+            required: true,
+            encoding: PhantomData,
+        })
+    }
+}
+
+impl<E> Representation for OptionallyPrefixed<E>
+where
+    E: Encoding,
+{
+    fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_prefixed::<E, S>(bytes, serializer)
+    }
+
+    fn deserialize<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Visitor<'de>,
+    {
+        deserializer.deserialize_str(PrefixedVisitor::<E, V> {
+            visitor,
+            required: false,
+            encoding: PhantomData,
+        })
+    }
+}
+
+fn serialize_prefixed<E, S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    E: Encoding,
+    S: Serializer,
+{
+    let mut text = String::from(E::PREFIX);
+    text.push_str(&E::encode(bytes));
+    serializer.serialize_str(&text)
+}
+
+struct PrefixedVisitor<E, V> {
+    visitor: V,
+    required: bool,
+    encoding: PhantomData<E>,
+}
+
+impl<'de, E, V> Visitor<'de> for PrefixedVisitor<E, V>
+where
+    E: Encoding,
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.required {
+            write!(
+                formatter,
+                "a {} string starting with `{}`",
+                E::NAME,
+                E::PREFIX
+            )
+        } else {
+            write!(formatter, "a {} string", E::NAME)
+        }
+    }
+
+    fn visit_str<Err>(self, v: &str) -> Result<V::Value, Err>
+    where
+        Err: de::Error,
+    {
+        let Some(digits) = v.strip_prefix(E::PREFIX) else {
+            if self.required {
+                return Err(Err::invalid_value(Unexpected::Str(v), &self));
+            }
+            return match E::decode(v) {
+                Ok(bytes) => self.visitor.visit_byte_buf(bytes),
+                Err(err) => Err(err.into_de_error(E::NAME, v)),
+            };
+        };
+        match E::decode(digits) {
+            Ok(bytes) => self.visitor.visit_byte_buf(bytes),
+            Err(err) => Err(err.after_prefix(E::PREFIX.len()).into_de_error(E::NAME, v)),
+        }
+    }
+}
+
+/// Lowercase hexadecimal, two digits per byte. Uppercase digits are accepted
+/// when deserializing.
+pub enum Hex {}
+
+impl Encoding for Hex {
+    const NAME: &'static str = "hex";
+    const PREFIX: &'static str = "0x";
+
+    fn encode(bytes: &[u8]) -> String {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let mut text = String::with_capacity(bytes.len() * 2);
+        for &byte in bytes {
+            text.push(char::from(DIGITS[usize::from(byte >> 4)]));
+            text.push(char::from(DIGITS[usize::from(byte & 0xf)]));
+        }
+        text
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        let digit = |i: usize| match text.as_bytes()[i] {
+            c @ b'0'..=b'9' => Ok(c - b'0'),
+            c @ b'a'..=b'f' => Ok(c - b'a' + 10),
+            c @ b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => Err(DecodeError::invalid_character(text, i)),
+        };
+
+        if text.len() % 2 != 0 {
+            return Err(DecodeError::invalid_length(text.len(), "a multiple of 2"));
+        }
+        (0..text.len())
+            .step_by(2)
+            .map(|i| Ok(digit(i)? << 4 | digit(i + 1)?))
+            .collect()
+    }
+}
+
+/// Base64 with the standard alphabet and padding, as in RFC 4648 section 4.
+pub enum Base64 {}
+
+impl Encoding for Base64 {
+    const NAME: &'static str = "base64";
+    const PREFIX: &'static str = "base64:";
+
+    fn encode(bytes: &[u8]) -> String {
+        base64_encode(bytes, BASE64_ALPHABET, true)
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        base64_decode(text, BASE64_ALPHABET, true)
+    }
+}
+
+/// Base64 with the URL and filename safe alphabet and no padding, as in RFC
+/// 4648 section 5.
+pub enum Base64Url {}
+
+impl Encoding for Base64Url {
+    const NAME: &'static str = "base64url";
+    const PREFIX: &'static str = "base64url:";
+
+    fn encode(bytes: &[u8]) -> String {
+        base64_encode(bytes, BASE64URL_ALPHABET, false)
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        base64_decode(text, BASE64URL_ALPHABET, false)
+    }
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+fn base64_encode(bytes: &[u8], alphabet: &[u8; 64], pad: bool) -> String {
+    let mut text = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let mut group = [0; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let bits = u32::from(group[0]) << 16 | u32::from(group[1]) << 8 | u32::from(group[2]);
+        let digits = chunk.len() + 1;
+        for i in 0..4 {
+            if i < digits {
+                let index = (bits >> (18 - 6 * i)) & 0x3f;
+                text.push(char::from(alphabet[index as usize]));
+            } else if pad {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+fn base64_decode(text: &str, alphabet: &[u8; 64], pad: bool) -> Result<Vec<u8>, DecodeError> {
+    let mut digits = text.as_bytes();
+    if pad {
+        if digits.len() % 4 != 0 {
+            return Err(DecodeError::invalid_length(digits.len(), "a multiple of 4"));
+        }
+        let padding = digits
+            .iter()
+            .rev()
+            .take(2)
+            .take_while(|&&c| c == b'=')
+            .count();
+        digits = &digits[..digits.len() - padding];
+    } else if digits.len() % 4 == 1 {
+        return Err(DecodeError::invalid_length(
+            digits.len(),
+            "not 1 more than a multiple of 4",
+        ));
+    }
+
+    let mut bytes = Vec::with_capacity(digits.len() * 3 / 4);
+    for (start, chunk) in (0..).step_by(4).zip(digits.chunks(4)) {
+        let mut bits = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let Some((value, _)) = (0..).zip(alphabet).find(|&(_, &a)| a == c) else {
+                return Err(DecodeError::invalid_character(text, start + i));
+            };
+            bits |= value << (18 - 6 * i);
+        }
+        let group = bits.to_be_bytes();
+        let len = chunk.len() - 1;
+        // Reject nonzero bits after the last full byte so that every byte
+        // string has exactly one encoding.
+        if group[1 + len..].iter().any(|&b| b != 0) {
+            return Err(DecodeError::non_canonical(start + len));
+        }
+        bytes.extend_from_slice(&group[1..=len]);
+    }
+    Ok(bytes)
+}
+
+/// Array of numbers, the same as a byte string in a human-readable format
+/// without `Adaptive`.
+pub enum Array {}
+
+impl Representation for Array {
+    fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(bytes.len()))?;
+        for byte in bytes {
+            seq.serialize_element(byte)?;
+        }
+        seq.end()
+    }
+
+    fn deserialize<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Visitor<'de>,
+    {
+        deserializer.deserialize_seq(visitor)
+    }
+}
+
+/// String holding the bytes unchanged, for bytes that are valid UTF-8.
+/// Serialization fails on bytes that are not.
+pub enum Utf8 {}
+
+impl Representation for Utf8 {
+    fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match str::from_utf8(bytes) {
+            Ok(string) => serializer.serialize_str(string),
+            Err(err) => Err(ser::Error::custom(format_args!(
+                "bytes are not valid UTF-8: {err}",
+            ))),
+        }
+    }
+
+    fn deserialize<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Visitor<'de>,
+    {
+        deserializer.deserialize_str(visitor)
+    }
+}
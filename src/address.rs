@@ -0,0 +1,76 @@
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+use serde::de::{Deserialize, DeserializeOwned, Deserializer, Error, Unexpected, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// A fixed-size value written as bytes in binary formats and as text in
+/// human-readable formats.
+pub trait Address: Sized {
+    #[doc(hidden)]
+    type Octets: Serialize + DeserializeOwned;
+
+    #[doc(hidden)]
+    const EXPECTING: &'static str;
+
+    #[doc(hidden)]
+    fn octets(&self) -> Self::Octets;
+
+    #[doc(hidden)]
+    fn from_octets<E: Error>(octets: Self::Octets) -> Result<Self, E>;
+
+    #[doc(hidden)]
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result;
+
+    #[doc(hidden)]
+    fn parse(text: &str) -> Option<Self>;
+}
+
+pub(crate) fn serialize<A, S>(address: &A, serializer: S) -> Result<S::Ok, S::Error>
+where
+    A: Address,
+    S: Serializer,
+{
+    struct Text<'a, A>(&'a A);
+
+    impl<'a, A: Address> Display for Text<'a, A> {
+        fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            self.0.fmt(formatter)
+        }
+    }
+
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Text(address))
+    } else {
+        address.octets().serialize(serializer)
+    }
+}
+
+pub(crate) fn deserialize<'de, A, D>(deserializer: D) -> Result<A, D::Error>
+where
+    A: Address,
+    D: Deserializer<'de>,
+{
+    struct TextVisitor<A>(PhantomData<A>);
+
+    impl<'de, A: Address> Visitor<'de> for TextVisitor<A> {
+        type Value = A;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str(A::EXPECTING)
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<A, E>
+        where
+            E: Error,
+        {
+            A::parse(v).ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+        }
+    }
+
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(TextVisitor(PhantomData))
+    } else {
+        let octets = A::Octets::deserialize(deserializer)?;
+        A::from_octets(octets)
+    }
+}
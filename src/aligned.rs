@@ -0,0 +1,623 @@
+//! Byte buffers whose data pointer has a guaranteed alignment.
+//!
+//! [`AlignedByteBuf`] and [`AlignedByteArray`] serialize and deserialize like
+//! [`ByteBuf`](crate::ByteBuf) and [`ByteArray`](crate::ByteArray), but store
+//! their bytes at an address that is a multiple of `ALIGN`, for consumers
+//! such as SIMD code or DMA engines that require it. Deserialization writes
+//! directly into the aligned storage.
+//!
+//! `ALIGN` must be a power of two from 1 to 4096.
+//!
+//! ```
+//! # use serde_derive::Deserialize;
+//! use serde::Deserialize;
+//! use serde_bytes::AlignedByteBuf;
+//!
+//! #[derive(Deserialize)]
+//! struct Block {
+//!     data: AlignedByteBuf<64>,
+//! }
+//!
+//! let block: Block = serde_json::from_str(r#"{"data":[1,2,3]}"#).unwrap();
+//! assert_eq!(*block.data, [1, 2, 3]);
+//! assert_eq!(block.data.as_ptr() as usize % 64, 0);
+//! ```
+
+use crate::Bytes;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+use core::{cmp, slice};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Marker for an alignment of `N` bytes.
+pub struct Align<const N: usize>;
+
+/// Implemented by [`Align<N>`] for each supported alignment.
+pub trait Alignment {
+    /// A type of size and alignment `N`.
+    type Chunk: Copy + 'static;
+
+    #[doc(hidden)]
+    const ZERO: Self::Chunk;
+}
+
+mod chunk {
+    macro_rules! chunks {
+        ($($n:literal $name:ident)*) => {
+            $(
+                #[derive(Copy, Clone)]
+                #[repr(C, align($n))]
+                pub struct $name(pub(super) [u8; $n]);
+
+                impl super::Alignment for super::Align<$n> {
+                    type Chunk = $name;
+
+                    const ZERO: $name = $name([0; $n]);
+                }
+            )*
+        };
+    }
+
+    chunks! {
+        1 Align1
+        2 Align2
+        4 Align4
+        8 Align8
+        16 Align16
+        32 Align32
+        64 Align64
+        128 Align128
+        256 Align256
+        512 Align512
+        1024 Align1024
+        2048 Align2048
+        4096 Align4096
+    }
+}
+
+/// Growable byte buffer whose data is aligned to `ALIGN` bytes.
+///
+/// The capacity is always a multiple of `ALIGN`.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub struct AlignedByteBuf<const ALIGN: usize>
+where
+    Align<ALIGN>: Alignment,
+{
+    // Every chunk is initialized, including those past `len`.
+    chunks: Vec<<Align<ALIGN> as Alignment>::Chunk>,
+    len: usize,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    /// Construct a new, empty `AlignedByteBuf`.
+    pub fn new() -> Self {
+        AlignedByteBuf {
+            chunks: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Construct a new, empty `AlignedByteBuf` with room for at least `cap`
+    /// bytes.
+    pub fn with_capacity(cap: usize) -> Self {
+        AlignedByteBuf {
+            chunks: Vec::with_capacity(chunk_count::<ALIGN>(cap)),
+            len: 0,
+        }
+    }
+
+    /// Append a byte to the end.
+    pub fn push(&mut self, byte: u8) {
+        let index = self.len;
+        self.resize(index + 1);
+        self.as_mut()[index] = byte;
+    }
+
+    /// Append bytes to the end.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let start = self.len;
+        self.resize(start + bytes.len());
+        self.as_mut()[start..].copy_from_slice(bytes);
+    }
+
+    /// Remove all bytes, keeping the capacity.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Shorten the buffer to `len` bytes, if it is longer.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            // Keep the bytes past `len` zero, as if never written.
+            self.as_mut()[len..].fill(0);
+            self.len = len;
+        }
+    }
+
+    /// Copy the bytes into an ordinary `Vec<u8>`.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.as_ref().to_vec()
+    }
+
+    fn resize(&mut self, len: usize) {
+        let chunks = chunk_count::<ALIGN>(len);
+        if chunks > self.chunks.len() {
+            self.chunks
+                .resize(chunks, <Align<ALIGN> as Alignment>::ZERO);
+        }
+        self.len = len;
+    }
+}
+
+// Number of chunks holding `len` bytes, without the overflow of
+// `(len + ALIGN - 1) / ALIGN`. A count too large to allocate makes `Vec`
+// panic with "capacity overflow".
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn chunk_count<const ALIGN: usize>(len: usize) -> usize {
+    len / ALIGN + usize::from(len % ALIGN != 0)
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> Clone for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn clone(&self) -> Self {
+        AlignedByteBuf {
+            chunks: self.chunks.clone(),
+            len: self.len,
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> Default for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn default() -> Self {
+        AlignedByteBuf::new()
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> Debug for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(self.as_ref(), f)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> AsRef<[u8]> for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn as_ref(&self) -> &[u8] {
+        // Each chunk is exactly ALIGN initialized bytes with no padding, and
+        // `len` never exceeds the total.
+        unsafe { slice::from_raw_parts(self.chunks.as_ptr().cast::<u8>(), self.len) }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> AsMut<[u8]> for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn as_mut(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.chunks.as_mut_ptr().cast::<u8>(), self.len) }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> Deref for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> DerefMut for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut()
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> Borrow<Bytes> for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn borrow(&self) -> &Bytes {
+        Bytes::new(self)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> From<&[u8]> for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn from(bytes: &[u8]) -> Self {
+        let mut buf = AlignedByteBuf::with_capacity(bytes.len());
+        buf.extend_from_slice(bytes);
+        buf
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<Rhs, const ALIGN: usize> PartialEq<Rhs> for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+    Rhs: ?Sized + AsRef<[u8]>,
+{
+    fn eq(&self, other: &Rhs) -> bool {
+        self.as_ref().eq(other.as_ref())
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> Eq for AlignedByteBuf<ALIGN> where Align<ALIGN>: Alignment {}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<Rhs, const ALIGN: usize> PartialOrd<Rhs> for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+    Rhs: ?Sized + AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Rhs) -> Option<Ordering> {
+        self.as_ref().partial_cmp(other.as_ref())
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> Ord for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_ref().cmp(other.as_ref())
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> Hash for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state);
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> Serialize for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+struct AlignedByteBufVisitor<const ALIGN: usize>;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de, const ALIGN: usize> Visitor<'de> for AlignedByteBufVisitor<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    type Value = AlignedByteBuf<ALIGN>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("byte array")
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let len = cmp::min(visitor.size_hint().unwrap_or(0), 4096);
+        let mut bytes = AlignedByteBuf::with_capacity(len);
+
+        while let Some(b) = visitor.next_element()? {
+            bytes.push(b);
+        }
+
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(AlignedByteBuf::from(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(AlignedByteBuf::from(v.as_bytes()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.visit_str(&v)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de, const ALIGN: usize> Deserialize<'de> for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(AlignedByteBufVisitor::<ALIGN>)
+    }
+}
+
+/// Wrapper around `[u8; N]` aligned to `ALIGN` bytes.
+///
+/// ```
+/// use serde_bytes::AlignedByteArray;
+///
+/// let key = AlignedByteArray::<32, 32>::new([7; 32]);
+/// assert_eq!(key.as_ptr() as usize % 32, 0);
+/// ```
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct AlignedByteArray<const N: usize, const ALIGN: usize>
+where
+    Align<ALIGN>: Alignment,
+{
+    align: [<Align<ALIGN> as Alignment>::Chunk; 0],
+    bytes: [u8; N],
+}
+
+impl<const N: usize, const ALIGN: usize> AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    /// Wrap an existing array into an `AlignedByteArray`.
+    pub const fn new(bytes: [u8; N]) -> Self {
+        AlignedByteArray { align: [], bytes }
+    }
+
+    /// Unwrap the byte array underlying this `AlignedByteArray`.
+    pub const fn into_array(self) -> [u8; N] {
+        self.bytes
+    }
+}
+
+impl<const N: usize, const ALIGN: usize> Debug for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.bytes, f)
+    }
+}
+
+impl<const N: usize, const ALIGN: usize> Default for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn default() -> Self {
+        AlignedByteArray::new([0; N])
+    }
+}
+
+impl<const N: usize, const ALIGN: usize> AsRef<[u8; N]> for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn as_ref(&self) -> &[u8; N] {
+        &self.bytes
+    }
+}
+
+impl<const N: usize, const ALIGN: usize> AsMut<[u8; N]> for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn as_mut(&mut self) -> &mut [u8; N] {
+        &mut self.bytes
+    }
+}
+
+impl<const N: usize, const ALIGN: usize> Borrow<[u8; N]> for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn borrow(&self) -> &[u8; N] {
+        &self.bytes
+    }
+}
+
+impl<const N: usize, const ALIGN: usize> Borrow<Bytes> for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn borrow(&self) -> &Bytes {
+        Bytes::new(&self.bytes)
+    }
+}
+
+impl<const N: usize, const ALIGN: usize> Deref for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    type Target = [u8; N];
+
+    fn deref(&self) -> &Self::Target {
+        &self.bytes
+    }
+}
+
+impl<const N: usize, const ALIGN: usize> DerefMut for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.bytes
+    }
+}
+
+impl<const N: usize, const ALIGN: usize> From<[u8; N]> for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn from(bytes: [u8; N]) -> Self {
+        AlignedByteArray::new(bytes)
+    }
+}
+
+impl<Rhs, const N: usize, const ALIGN: usize> PartialEq<Rhs> for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+    Rhs: ?Sized + Borrow<[u8; N]>,
+{
+    fn eq(&self, other: &Rhs) -> bool {
+        self.bytes.eq(other.borrow())
+    }
+}
+
+impl<const N: usize, const ALIGN: usize> Eq for AlignedByteArray<N, ALIGN> where
+    Align<ALIGN>: Alignment
+{
+}
+
+impl<Rhs, const N: usize, const ALIGN: usize> PartialOrd<Rhs> for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+    Rhs: ?Sized + Borrow<[u8; N]>,
+{
+    fn partial_cmp(&self, other: &Rhs) -> Option<Ordering> {
+        self.bytes.partial_cmp(other.borrow())
+    }
+}
+
+impl<const N: usize, const ALIGN: usize> Ord for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes.cmp(&other.bytes)
+    }
+}
+
+impl<const N: usize, const ALIGN: usize> Hash for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state);
+    }
+}
+
+impl<const N: usize, const ALIGN: usize> Serialize for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.bytes)
+    }
+}
+
+impl<'de, const N: usize, const ALIGN: usize> Deserialize<'de> for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(AlignedByteArrayVisitor::<N, ALIGN>)
+    }
+}
+
+struct AlignedByteArrayVisitor<const N: usize, const ALIGN: usize>;
+
+impl<'de, const N: usize, const ALIGN: usize> Visitor<'de> for AlignedByteArrayVisitor<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    type Value = AlignedByteArray<N, ALIGN>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a byte array of length {N}")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut array = AlignedByteArray::default();
+
+        for (idx, byte) in array.bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| V::Error::invalid_length(idx, &self))?;
+        }
+
+        Ok(array)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        if v.len() != N {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        let mut array = AlignedByteArray::default();
+        array.bytes.copy_from_slice(v);
+        Ok(array)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.visit_bytes(v.as_bytes())
+    }
+}
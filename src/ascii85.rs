@@ -0,0 +1,128 @@
+//! Ascii85 text in human-readable formats, a native byte string otherwise.
+//!
+//! Ascii85 encodes each 4 bytes as 5 characters from `!` to `u`, and a group
+//! of 4 zero bytes as the single character `z`. A final group of fewer than
+//! 4 bytes is written as one character more than it has bytes. The `<~` and
+//! `~>` delimiters used by PostScript are not written or accepted.
+//!
+//! Deserialization only accepts the text that serializing the same bytes
+//! would produce, so every byte string has exactly one encoding.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Blob {
+//!     #[serde(with = "serde_bytes::ascii85")]
+//!     data: Vec<u8>,
+//! }
+//!
+//! let blob = Blob {
+//!     data: b"sure.".to_vec(),
+//! };
+//! let json = serde_json::to_string(&blob).unwrap();
+//! assert_eq!(json, r#"{"data":"F*2M7/c"}"#);
+//! ```
+
+use crate::adaptive::{Adaptive, DecodeError, Encoding};
+use crate::de::Deserialize;
+use crate::ser::Serialize;
+use serde::{Deserializer, Serializer};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Serde `serialize_with` function to serialize bytes as Ascii85.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Adaptive::<Ascii85>::serialize(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize bytes from Ascii85.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Adaptive::<Ascii85>::deserialize(deserializer)
+}
+
+/// Ascii85 without delimiters, with `z` for a group of zero bytes.
+pub enum Ascii85 {}
+
+impl Encoding for Ascii85 {
+    const NAME: &'static str = "ascii85";
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn encode(bytes: &[u8]) -> String {
+        let mut text = String::with_capacity((bytes.len() + 3) / 4 * 5);
+        for chunk in bytes.chunks(4) {
+            if chunk == [0; 4] {
+                text.push('z');
+                continue;
+            }
+            let mut group = [0; 4];
+            group[..chunk.len()].copy_from_slice(chunk);
+            let mut value = u32::from_be_bytes(group);
+            let mut digits = [0; 5];
+            for digit in digits.iter_mut().rev() {
+                *digit = b'!' + (value % 85) as u8;
+                value /= 85;
+            }
+            text.extend(digits[..=chunk.len()].iter().map(|&c| char::from(c)));
+        }
+        text
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        let digits = text.as_bytes();
+        let mut bytes = Vec::with_capacity(digits.len() / 5 * 4);
+        let mut start = 0;
+        while start < digits.len() {
+            if digits[start] == b'z' {
+                bytes.extend_from_slice(&[0; 4]);
+                start += 1;
+                continue;
+            }
+            let len = (digits.len() - start).min(5);
+            if len == 1 {
+                return Err(DecodeError::invalid_length(
+                    digits.len(),
+                    "not 1 more than a multiple of 5, counting each `z` as 5",
+                ));
+            }
+            // A short final group is padded with the largest digit, then the
+            // extra bytes are dropped. A digit is also invalid if it makes
+            // the group exceed 32 bits.
+            let mut value = 0u32;
+            for i in 0..5 {
+                let offset = start + i.min(len - 1);
+                let digit = match digits[offset] {
+                    _ if i >= len => Some(84),
+                    c @ b'!'..=b'u' => Some(c - b'!'),
+                    _ => None,
+                };
+                match digit.and_then(|d| value.checked_mul(85)?.checked_add(u32::from(d))) {
+                    Some(next) => value = next,
+                    None => return Err(DecodeError::invalid_character(text, offset)),
+                }
+            }
+            bytes.extend_from_slice(&value.to_be_bytes()[..len - 1]);
+            start += len;
+        }
+
+        // Reject other spellings of the same bytes, such as `!!!!!` for `z`.
+        let canonical = Self::encode(&bytes);
+        if canonical != text {
+            let same = canonical.bytes().zip(digits).take_while(|(a, &b)| *a == b);
+            return Err(DecodeError::non_canonical(same.count()));
+        }
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,536 @@
+//! Detect byte fields that are serialized as a sequence of integers.
+//!
+//! A `Vec<u8>` or `[u8; N]` field that is missing `#[serde(with =
+//! "serde_bytes")]` still round-trips correctly, it is just serialized one
+//! `u8` at a time. The [`Auditor`] serializer walks a value and records the
+//! path of every non-empty sequence or tuple whose elements are all `u8`, so
+//! that a test can fail when such a field slips in.
+//!
+//! ```
+//! # use serde_derive::Serialize;
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct Header {
+//!     #[serde(with = "serde_bytes")]
+//!     key_id: Vec<u8>,
+//!     nonce: [u8; 12],
+//! }
+//!
+//! #[derive(Serialize)]
+//! struct Message {
+//!     header: Header,
+//!     payload: Vec<u8>,
+//! }
+//!
+//! let message = Message {
+//!     header: Header {
+//!         key_id: vec![1],
+//!         nonce: [0; 12],
+//!     },
+//!     payload: vec![2, 3],
+//! };
+//!
+//! let err = serde_bytes::audit::check(&message).unwrap_err();
+//! assert_eq!(err.paths(), ["header.nonce", "payload"]);
+//! ```
+//!
+//! Empty sequences have no elements to inspect and are never reported, so
+//! audit a value in which every byte field is populated.
+
+use core::fmt::{self, Display, Write as _};
+use serde::ser::{
+    self, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Serialize `value` and fail if any sequence of `u8` was emitted.
+pub fn check<T>(value: &T) -> Result<(), Error>
+where
+    T: ?Sized + Serialize,
+{
+    let mut auditor = Auditor::new();
+    value.serialize(&mut auditor)?;
+    if auditor.paths.is_empty() {
+        Ok(())
+    } else {
+        Err(Error {
+            kind: ErrorKind::Unoptimized(auditor.paths),
+        })
+    }
+}
+
+/// Serializer that records where a sequence of `u8` was serialized.
+///
+/// Paths are written with `.field` for struct fields and enum variants,
+/// `[index]` for sequence elements, and `[key]` for map values. The root
+/// value has the empty path.
+#[derive(Default)]
+pub struct Auditor {
+    path: Vec<Segment>,
+    paths: Vec<String>,
+    last_was_u8: bool,
+    key: Option<String>,
+}
+
+enum Segment {
+    Name(&'static str),
+    Index(usize),
+    Key(String),
+}
+
+impl Auditor {
+    /// Construct an auditor that has not recorded anything yet.
+    pub fn new() -> Self {
+        Auditor::default()
+    }
+
+    /// Paths of the sequences of `u8` recorded so far.
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    /// Unwrap the paths of the sequences of `u8` recorded so far.
+    pub fn into_paths(self) -> Vec<String> {
+        self.paths
+    }
+
+    fn current_path(&self) -> String {
+        let mut path = String::new();
+        for segment in &self.path {
+            match segment {
+                Segment::Name(name) => {
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(name);
+                }
+                Segment::Index(index) => {
+                    let _ = write!(path, "[{index}]");
+                }
+                Segment::Key(key) => {
+                    let _ = write!(path, "[{key}]");
+                }
+            }
+        }
+        path
+    }
+
+    fn scalar(&mut self, is_u8: bool, key: impl Display) {
+        self.last_was_u8 = is_u8;
+        if let Some(slot) = &mut self.key {
+            *slot = key.to_string();
+        }
+    }
+
+    fn nested<T>(&mut self, segment: Segment, value: &T) -> Result<bool, Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.path.push(segment);
+        let result = value.serialize(&mut *self);
+        self.path.pop();
+        result?;
+        Ok(self.last_was_u8)
+    }
+}
+
+/// State of the [`Auditor`] while it serializes a compound value.
+pub struct Compound<'a> {
+    auditor: &'a mut Auditor,
+    len: usize,
+    all_u8: bool,
+    check: bool,
+}
+
+impl<'a> Compound<'a> {
+    fn new(auditor: &'a mut Auditor, check: bool) -> Self {
+        Compound {
+            auditor,
+            len: 0,
+            all_u8: true,
+            check,
+        }
+    }
+
+    fn element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let is_u8 = self.auditor.nested(Segment::Index(self.len), value)?;
+        self.all_u8 &= is_u8;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn field<T>(&mut self, name: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.auditor.nested(Segment::Name(name), value)?;
+        Ok(())
+    }
+
+    fn finish(self) {
+        if self.check && self.all_u8 && self.len > 0 {
+            let path = self.auditor.current_path();
+            self.auditor.paths.push(path);
+        }
+        self.auditor.last_was_u8 = false;
+    }
+}
+
+macro_rules! scalars {
+    ($($method:ident($ty:ty) $is_u8:literal)*) => {
+        $(
+            fn $method(self, v: $ty) -> Result<(), Error> {
+                self.scalar($is_u8, v);
+                Ok(())
+            }
+        )*
+    };
+}
+
+impl<'a> Serializer for &'a mut Auditor {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    scalars! {
+        serialize_bool(bool) false
+        serialize_i8(i8) false
+        serialize_i16(i16) false
+        serialize_i32(i32) false
+        serialize_i64(i64) false
+        serialize_i128(i128) false
+        serialize_u8(u8) true
+        serialize_u16(u16) false
+        serialize_u32(u32) false
+        serialize_u64(u64) false
+        serialize_u128(u128) false
+        serialize_f32(f32) false
+        serialize_f64(f64) false
+        serialize_char(char) false
+        serialize_str(&str) false
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), Error> {
+        self.scalar(false, "?");
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.scalar(false, "None");
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut *self)?;
+        self.last_was_u8 = false;
+        Ok(())
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.scalar(false, "()");
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), Error> {
+        self.scalar(false, name);
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.scalar(false, variant);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut *self)?;
+        self.last_was_u8 = false;
+        Ok(())
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.nested(Segment::Name(variant), value)?;
+        self.last_was_u8 = false;
+        Ok(())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a>, Error> {
+        Ok(Compound::new(self, true))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Compound<'a>, Error> {
+        Ok(Compound::new(self, true))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        Ok(Compound::new(self, false))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        self.path.push(Segment::Name(variant));
+        Ok(Compound::new(self, false))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a>, Error> {
+        Ok(Compound::new(self, false))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Compound<'a>, Error> {
+        Ok(Compound::new(self, false))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        self.path.push(Segment::Name(variant));
+        Ok(Compound::new(self, false))
+    }
+}
+
+impl<'a> SerializeSeq for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish();
+        Ok(())
+    }
+}
+
+impl<'a> SerializeTuple for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish();
+        Ok(())
+    }
+}
+
+impl<'a> SerializeTupleStruct for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish();
+        Ok(())
+    }
+}
+
+impl<'a> SerializeTupleVariant for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.auditor.path.pop();
+        self.finish();
+        Ok(())
+    }
+}
+
+impl<'a> SerializeMap for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let outer = self.auditor.key.replace(String::from("?"));
+        let result = key.serialize(&mut *self.auditor);
+        let key = self.auditor.key.take();
+        self.auditor.key = outer;
+        result?;
+        self.auditor
+            .path
+            .push(Segment::Key(key.unwrap_or_default()));
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let outer = self.auditor.key.take();
+        let result = value.serialize(&mut *self.auditor);
+        self.auditor.key = outer;
+        self.auditor.path.pop();
+        result
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish();
+        Ok(())
+    }
+}
+
+impl<'a> SerializeStruct for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish();
+        Ok(())
+    }
+}
+
+impl<'a> SerializeStructVariant for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.auditor.path.pop();
+        self.finish();
+        Ok(())
+    }
+}
+
+/// Error returned by [`check`].
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Unoptimized(Vec<String>),
+    Custom(String),
+}
+
+impl Error {
+    /// Paths at which a sequence of `u8` was serialized.
+    ///
+    /// This is empty if the error came from the value's `Serialize` impl
+    /// rather than from the audit.
+    pub fn paths(&self) -> &[String] {
+        match &self.kind {
+            ErrorKind::Unoptimized(paths) => paths,
+            ErrorKind::Custom(_) => &[],
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            ErrorKind::Unoptimized(paths) => {
+                formatter.write_str("sequence of u8 serialized at ")?;
+                for (i, path) in paths.iter().enumerate() {
+                    if i > 0 {
+                        formatter.write_str(", ")?;
+                    }
+                    if path.is_empty() {
+                        formatter.write_str("the root")?;
+                    } else {
+                        write!(formatter, "`{path}`")?;
+                    }
+                }
+                formatter.write_str("; use #[serde(with = \"serde_bytes\")]")
+            }
+            ErrorKind::Custom(msg) => formatter.write_str(msg),
+        }
+    }
+}
+
+impl ser::StdError for Error {}
+
+impl ser::Error for Error {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        Error {
+            kind: ErrorKind::Custom(msg.to_string()),
+        }
+    }
+}
@@ -0,0 +1,317 @@
+//! Base32 text in human-readable formats, a native byte string otherwise.
+//!
+//! The functions at the top of this module use the RFC 4648 alphabet with
+//! padding. The submodules [`unpadded`], [`hex`] and [`crockford`] provide
+//! the other common variants. Text is written in uppercase, and either case
+//! is accepted when deserializing.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Totp {
+//!     #[serde(with = "serde_bytes::base32::unpadded")]
+//!     secret: Vec<u8>,
+//! }
+//!
+//! let json = r#"{"secret":"jbswy3dpehpk3pxp"}"#;
+//! let totp: Totp = serde_json::from_str(json).unwrap();
+//! assert_eq!(totp.secret, b"Hello!\xde\xad\xbe\xef");
+//!
+//! let json = serde_json::to_string(&totp).unwrap();
+//! assert_eq!(json, r#"{"secret":"JBSWY3DPEHPK3PXP"}"#);
+//! ```
+
+use crate::adaptive::{Adaptive, DecodeError, Encoding};
+use crate::de::Deserialize;
+use crate::ser::Serialize;
+use serde::{Deserializer, Serializer};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Serde `serialize_with` function to serialize bytes as padded Base32.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Adaptive::<Base32>::serialize(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize bytes from padded
+/// Base32.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Adaptive::<Base32>::deserialize(deserializer)
+}
+
+/// RFC 4648 alphabet without padding, as commonly used for TOTP secrets.
+pub mod unpadded {
+    use super::Base32Unpadded;
+    use crate::adaptive::Adaptive;
+    use crate::de::Deserialize;
+    use crate::ser::Serialize;
+    use serde::{Deserializer, Serializer};
+
+    /// Serde `serialize_with` function to serialize bytes as unpadded
+    /// Base32.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        Adaptive::<Base32Unpadded>::serialize(bytes, serializer)
+    }
+
+    /// Serde `deserialize_with` function to deserialize bytes from unpadded
+    /// Base32.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Adaptive::<Base32Unpadded>::deserialize(deserializer)
+    }
+}
+
+/// RFC 4648 extended hex alphabet with padding, which preserves the sort
+/// order of the bytes.
+pub mod hex {
+    use super::Base32Hex;
+    use crate::adaptive::Adaptive;
+    use crate::de::Deserialize;
+    use crate::ser::Serialize;
+    use serde::{Deserializer, Serializer};
+
+    /// Serde `serialize_with` function to serialize bytes as Base32 with
+    /// the extended hex alphabet.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        Adaptive::<Base32Hex>::serialize(bytes, serializer)
+    }
+
+    /// Serde `deserialize_with` function to deserialize bytes from Base32
+    /// with the extended hex alphabet.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Adaptive::<Base32Hex>::deserialize(deserializer)
+    }
+}
+
+/// Crockford's alphabet without padding, for identifiers read and typed by
+/// people.
+pub mod crockford {
+    use super::Crockford;
+    use crate::adaptive::Adaptive;
+    use crate::de::Deserialize;
+    use crate::ser::Serialize;
+    use serde::{Deserializer, Serializer};
+
+    /// Serde `serialize_with` function to serialize bytes as Crockford
+    /// Base32.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        Adaptive::<Crockford>::serialize(bytes, serializer)
+    }
+
+    /// Serde `deserialize_with` function to deserialize bytes from Crockford
+    /// Base32.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Adaptive::<Crockford>::deserialize(deserializer)
+    }
+}
+
+/// Base32 with the RFC 4648 alphabet and padding, as in RFC 4648 section 6.
+pub enum Base32 {}
+
+impl Encoding for Base32 {
+    const NAME: &'static str = "base32";
+
+    fn encode(bytes: &[u8]) -> String {
+        encode(bytes, BASE32_ALPHABET, true)
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        decode(text, base32_digit, true, false)
+    }
+}
+
+/// Base32 with the RFC 4648 alphabet and no padding.
+pub enum Base32Unpadded {}
+
+impl Encoding for Base32Unpadded {
+    const NAME: &'static str = "base32nopad";
+
+    fn encode(bytes: &[u8]) -> String {
+        encode(bytes, BASE32_ALPHABET, false)
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        decode(text, base32_digit, false, false)
+    }
+}
+
+/// Base32 with the extended hex alphabet and padding, as in RFC 4648 section
+/// 7.
+pub enum Base32Hex {}
+
+impl Encoding for Base32Hex {
+    const NAME: &'static str = "base32hex";
+
+    fn encode(bytes: &[u8]) -> String {
+        encode(bytes, BASE32HEX_ALPHABET, true)
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        decode(text, base32hex_digit, true, false)
+    }
+}
+
+/// Crockford's Base32 without padding or check symbol. When deserializing,
+/// hyphens are ignored and `O`, `I` and `L` are read as the digits they
+/// resemble.
+pub enum Crockford {}
+
+impl Encoding for Crockford {
+    const NAME: &'static str = "crockford base32";
+
+    fn encode(bytes: &[u8]) -> String {
+        encode(bytes, CROCKFORD_ALPHABET, false)
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        decode(text, crockford_digit, false, true)
+    }
+}
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE32HEX_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+const CROCKFORD_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+fn base32_digit(c: u8) -> Option<u8> {
+    match c.to_ascii_uppercase() {
+        c @ b'A'..=b'Z' => Some(c - b'A'),
+        c @ b'2'..=b'7' => Some(c - b'2' + 26),
+        _ => None,
+    }
+}
+
+fn base32hex_digit(c: u8) -> Option<u8> {
+    match c.to_ascii_uppercase() {
+        c @ b'0'..=b'9' => Some(c - b'0'),
+        c @ b'A'..=b'V' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn crockford_digit(c: u8) -> Option<u8> {
+    match c.to_ascii_uppercase() {
+        b'O' => Some(0),
+        b'I' | b'L' => Some(1),
+        c => (0..)
+            .zip(CROCKFORD_ALPHABET)
+            .find(|&(_, &a)| a == c)
+            .map(|(i, _)| i),
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn encode(bytes: &[u8], alphabet: &[u8; 32], pad: bool) -> String {
+    let mut text = String::with_capacity((bytes.len() + 4) / 5 * 8);
+    for chunk in bytes.chunks(5) {
+        let mut group = [0; 8];
+        group[3..3 + chunk.len()].copy_from_slice(chunk);
+        let bits = u64::from_be_bytes(group);
+        let digits = (chunk.len() * 8 + 4) / 5;
+        for i in 0..8 {
+            if i < digits {
+                let index = (bits >> (35 - 5 * i)) & 0x1f;
+                text.push(char::from(alphabet[index as usize]));
+            } else if pad {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+fn decode(
+    text: &str,
+    digit: fn(u8) -> Option<u8>,
+    pad: bool,
+    skip_hyphens: bool,
+) -> Result<Vec<u8>, DecodeError> {
+    // Offset and value of each character that carries a digit.
+    let chars: Vec<(usize, u8)> = text
+        .bytes()
+        .enumerate()
+        .filter(|&(_, c)| !(skip_hyphens && c == b'-'))
+        .collect();
+    let mut chars = &chars[..];
+    if pad {
+        if chars.len() % 8 != 0 {
+            return Err(DecodeError::invalid_length(chars.len(), "a multiple of 8"));
+        }
+        let padding = chars
+            .iter()
+            .rev()
+            .take(6)
+            .take_while(|&&(_, c)| c == b'=')
+            .count();
+        chars = &chars[..chars.len() - padding];
+    }
+    if let 1 | 3 | 6 = chars.len() % 8 {
+        return Err(if pad {
+            // The padding starts where a digit is still needed.
+            DecodeError::invalid_character(text, chars.len())
+        } else {
+            // Hyphens are not counted, so say that the length is in digits.
+            let expected = if skip_hyphens {
+                "a number of digits not 1, 3 or 6 more than a multiple of 8"
+            } else {
+                "not 1, 3 or 6 more than a multiple of 8"
+            };
+            DecodeError::invalid_length(chars.len(), expected)
+        });
+    }
+
+    let mut bytes = Vec::with_capacity(chars.len() * 5 / 8);
+    for chunk in chars.chunks(8) {
+        let mut bits = 0u64;
+        for (i, &(offset, c)) in chunk.iter().enumerate() {
+            let Some(value) = digit(c) else {
+                return Err(DecodeError::invalid_character(text, offset));
+            };
+            bits |= u64::from(value) << (35 - 5 * i);
+        }
+        let group = bits.to_be_bytes();
+        let len = chunk.len() * 5 / 8;
+        // Reject nonzero bits after the last full byte so that every byte
+        // string has exactly one encoding.
+        if group[3 + len..].iter().any(|&b| b != 0) {
+            return Err(DecodeError::non_canonical(chunk[chunk.len() - 1].0));
+        }
+        bytes.extend_from_slice(&group[3..3 + len]);
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,124 @@
+//! Base58 text in human-readable formats, a native byte string otherwise.
+//!
+//! Base58 uses the Bitcoin alphabet, which leaves out the easily confused
+//! characters `0`, `O`, `I` and `l`. Each leading zero byte is written as a
+//! leading `1`.
+//!
+//! Base58 is not a power-of-two base, so each digit changes every byte
+//! before it. Encoding and decoding take time quadratic in the length: a
+//! 10 KB string decodes in milliseconds, a 100 KB one takes seconds. Bound
+//! the length of untrusted input before it reaches this module, for example
+//! with a limit on the size of the whole document.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//! use serde_bytes::ByteArray;
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Transaction {
+//!     #[serde(with = "serde_bytes::base58")]
+//!     hash: ByteArray<32>,
+//! }
+//! ```
+
+use crate::adaptive::{Adaptive, DecodeError, Encoding};
+use crate::de::Deserialize;
+use crate::ser::Serialize;
+use serde::{Deserializer, Serializer};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Serde `serialize_with` function to serialize bytes as Base58.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Adaptive::<Base58>::serialize(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize bytes from Base58.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Adaptive::<Base58>::deserialize(deserializer)
+}
+
+/// Base58 with the Bitcoin alphabet.
+pub enum Base58 {}
+
+impl Encoding for Base58 {
+    const NAME: &'static str = "base58";
+
+    fn encode(bytes: &[u8]) -> String {
+        encode(bytes)
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        decode(text)
+    }
+}
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+
+    // Base58 digits of the remaining bytes, least significant first.
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &byte in &bytes[zeros..] {
+        let mut carry = u32::from(byte);
+        for digit in &mut digits {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut text = String::with_capacity(zeros + digits.len());
+    for _ in 0..zeros {
+        text.push('1');
+    }
+    for &digit in digits.iter().rev() {
+        text.push(char::from(ALPHABET[usize::from(digit)]));
+    }
+    text
+}
+
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+    let digits = text.as_bytes();
+    let zeros = digits.iter().take_while(|&&c| c == b'1').count();
+
+    // Bytes of the remaining digits, least significant first.
+    let mut bytes: Vec<u8> = Vec::with_capacity(digits.len() * 733 / 1000 + 1);
+    for (offset, &c) in digits.iter().enumerate().skip(zeros) {
+        let Some((mut carry, _)) = (0..).zip(ALPHABET).find(|&(_, &a)| a == c) else {
+            return Err(DecodeError::invalid_character(text, offset));
+        };
+        for byte in &mut bytes {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    bytes.resize(bytes.len() + zeros, 0);
+    bytes.reverse();
+    Ok(bytes)
+}
@@ -0,0 +1,124 @@
+//! `Base58Check` text in human-readable formats, a native byte string
+//! otherwise.
+//!
+//! `Base58Check` appends the first 4 bytes of the double SHA-256 of the data
+//! before encoding it as [Base58](crate::base58), so that a mistyped string
+//! is detected when deserializing. Any version byte is considered part of the
+//! data.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//! use serde_bytes::ByteArray;
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Address {
+//!     // Version byte followed by a 20-byte hash.
+//!     #[serde(with = "serde_bytes::base58check")]
+//!     address: ByteArray<21>,
+//! }
+//!
+//! let json = r#"{"address":"1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2"}"#;
+//! let address: Address = serde_json::from_str(json).unwrap();
+//! assert_eq!(address.address[0], 0);
+//!
+//! let typo = r#"{"address":"1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN3"}"#;
+//! let err = serde_json::from_str::<Address>(typo).err().unwrap();
+//! assert_eq!(
+//!     err.to_string(),
+//!     "invalid value: string \"1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN3\", expected a base58check string with a valid checksum at line 1 column 47",
+//! );
+//! ```
+
+use crate::adaptive::{Adaptive, Representation};
+use crate::de::Deserialize;
+use crate::ser::Serialize;
+use crate::{base58, sha256};
+use core::fmt;
+use serde::de::{self, Unexpected, Visitor};
+use serde::{Deserializer, Serializer};
+
+/// Serde `serialize_with` function to serialize bytes as `Base58Check`.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Adaptive::<Base58Check>::serialize(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize bytes from `Base58Check`.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Adaptive::<Base58Check>::deserialize(deserializer)
+}
+
+/// Base58 with a 4-byte double SHA-256 checksum.
+pub enum Base58Check {}
+
+fn checksum(bytes: &[u8]) -> [u8; 4] {
+    let hash = sha256::digest(&sha256::digest(bytes));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+impl Representation for Base58Check {
+    fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut data = bytes.to_vec();
+        data.extend_from_slice(&checksum(bytes));
+        serializer.serialize_str(&base58::encode(&data))
+    }
+
+    fn deserialize<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Visitor<'de>,
+    {
+        deserializer.deserialize_str(Base58CheckVisitor { visitor })
+    }
+}
+
+struct Base58CheckVisitor<V> {
+    visitor: V,
+}
+
+impl<'de, V> Visitor<'de> for Base58CheckVisitor<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a base58check string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        let mut data = match base58::decode(v) {
+            Ok(data) => data,
+            Err(err) => return Err(err.into_de_error("base58check", v)),
+        };
+        if data.len() < 4 {
+            return Err(E::invalid_value(
+                Unexpected::Str(v),
+                &"a base58check string with a 4-byte checksum",
+            ));
+        }
+        let payload_len = data.len() - 4;
+        if data[payload_len..] != checksum(&data[..payload_len]) {
+            return Err(E::invalid_value(
+                Unexpected::Str(v),
+                &"a base58check string with a valid checksum",
+            ));
+        }
+        data.truncate(payload_len);
+        self.visitor.visit_byte_buf(data)
+    }
+}
@@ -0,0 +1,295 @@
+//! Bech32 and Bech32m text with a fixed human-readable part.
+//!
+//! [`Bech32<H>`] and [`Bech32m<H>`] are [representations] for use with
+//! [`Adaptive`], so binary formats still get a native byte string. The
+//! human-readable part comes from a type implementing [`Hrp`], and is checked
+//! along with the checksum when deserializing.
+//!
+//! The whole byte string is converted to 5-bit groups, as done by Nostr and
+//! Lightning. Segwit addresses, which put a witness version in front of the
+//! converted program, are not covered. The 90 character limit of BIP 173 is
+//! not enforced.
+//!
+//! [representations]: crate::adaptive::Representation
+//! [`Adaptive`]: crate::adaptive::Adaptive
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//! use serde_bytes::adaptive::Adaptive;
+//! use serde_bytes::bech32::{Bech32, Hrp};
+//! use serde_bytes::ByteArray;
+//!
+//! enum Npub {}
+//!
+//! impl Hrp for Npub {
+//!     const HRP: &'static str = "npub";
+//! }
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Profile {
+//!     #[serde(with = "Adaptive::<Bech32<Npub>>")]
+//!     pubkey: ByteArray<32>,
+//! }
+//!
+//! let json = r#"{"pubkey":"npub10elfcs4fr0l0r8af98jlmgdh9c8tcxjvz9qkw038js35mp4dma8qzvjptg"}"#;
+//! let profile: Profile = serde_json::from_str(json).unwrap();
+//! assert_eq!(profile.pubkey[..4], [0x7e, 0x7e, 0x9c, 0x42]);
+//! assert_eq!(serde_json::to_string(&profile).unwrap(), json);
+//! ```
+
+use crate::adaptive::{DecodeError, Representation};
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use serde::ser::Serializer;
+
+#[cfg(feature = "alloc")]
+use alloc::format;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Human-readable part of a Bech32 string, such as `"npub"`.
+pub trait Hrp {
+    /// The human-readable part in lowercase. Deserialization accepts it in
+    /// either case, like the rest of the string.
+    const HRP: &'static str;
+}
+
+/// Bech32 as in BIP 173.
+pub struct Bech32<H> {
+    hrp: PhantomData<H>,
+}
+
+/// Bech32m as in BIP 350, which fixes a weakness of the Bech32 checksum.
+pub struct Bech32m<H> {
+    hrp: PhantomData<H>,
+}
+
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+impl<H> Representation for Bech32<H>
+where
+    H: Hrp,
+{
+    fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&encode(H::HRP, bytes, BECH32_CONST))
+    }
+
+    fn deserialize<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Visitor<'de>,
+    {
+        deserializer.deserialize_str(Bech32Visitor {
+            visitor,
+            name: "bech32",
+            hrp: H::HRP,
+            constant: BECH32_CONST,
+        })
+    }
+}
+
+impl<H> Representation for Bech32m<H>
+where
+    H: Hrp,
+{
+    fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&encode(H::HRP, bytes, BECH32M_CONST))
+    }
+
+    fn deserialize<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Visitor<'de>,
+    {
+        deserializer.deserialize_str(Bech32Visitor {
+            visitor,
+            name: "bech32m",
+            hrp: H::HRP,
+            constant: BECH32M_CONST,
+        })
+    }
+}
+
+struct Bech32Visitor<V> {
+    visitor: V,
+    name: &'static str,
+    hrp: &'static str,
+    constant: u32,
+}
+
+impl<'de, V> Visitor<'de> for Bech32Visitor<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a {} string with human-readable part `{}`",
+            self.name, self.hrp,
+        )
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        match decode(self.hrp, v, self.constant) {
+            Ok(bytes) => self.visitor.visit_byte_buf(bytes),
+            Err(Invalid::Decode(err)) => Err(err.into_de_error(self.name, v)),
+            Err(Invalid::Hrp) => Err(E::invalid_value(Unexpected::Str(v), &self)),
+            Err(Invalid::NoChecksum) => {
+                let expected = format!("a {} string with a 6-character checksum", self.name);
+                Err(E::invalid_value(Unexpected::Str(v), &expected.as_str()))
+            }
+            Err(Invalid::Checksum) => {
+                let expected = format!("a {} string with a valid checksum", self.name);
+                Err(E::invalid_value(Unexpected::Str(v), &expected.as_str()))
+            }
+        }
+    }
+}
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn polymod(values: impl IntoIterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ];
+    let mut chk = 1u32;
+    for value in values {
+        let top = chk >> 25;
+        chk = (chk & 0x01ff_ffff) << 5 ^ u32::from(value);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+fn hrp_expand(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    let hrp = hrp.as_bytes();
+    let high = hrp.iter().map(|c| c >> 5);
+    let low = hrp.iter().map(|c| c & 0x1f);
+    high.chain([0]).chain(low)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn encode(hrp: &str, bytes: &[u8], constant: u32) -> String {
+    // Split the bytes into 5-bit groups, padding the last one with zeros.
+    let mut data = Vec::with_capacity((bytes.len() * 8 + 4) / 5 + 6);
+    let mut acc = 0u16;
+    let mut bits = 0;
+    for &byte in bytes {
+        acc = acc << 8 | u16::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            data.push((acc >> bits) as u8 & 0x1f);
+        }
+    }
+    if bits > 0 {
+        data.push((acc << (5 - bits)) as u8 & 0x1f);
+    }
+
+    let checksum = polymod(hrp_expand(hrp).chain(data.iter().copied()).chain([0; 6])) ^ constant;
+    for i in 0..6 {
+        data.push((checksum >> (5 * (5 - i))) as u8 & 0x1f);
+    }
+
+    let mut text = String::with_capacity(hrp.len() + 1 + data.len());
+    text.push_str(hrp);
+    text.push('1');
+    for value in data {
+        text.push(char::from(CHARSET[usize::from(value)]));
+    }
+    text
+}
+
+enum Invalid {
+    Decode(DecodeError),
+    Hrp,
+    NoChecksum,
+    Checksum,
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn decode(hrp: &str, text: &str, constant: u32) -> Result<Vec<u8>, Invalid> {
+    let invalid_character = |offset| Invalid::Decode(DecodeError::invalid_character(text, offset));
+
+    // The string must be all lowercase or all uppercase. Point at the first
+    // character whose case differs from the first letter's.
+    let first_letter = text.bytes().find(u8::is_ascii_alphabetic);
+    if let Some(upper) = first_letter.map(|c| c.is_ascii_uppercase()) {
+        let mixed = text.bytes().position(|c| {
+            if upper {
+                c.is_ascii_lowercase()
+            } else {
+                c.is_ascii_uppercase()
+            }
+        });
+        if let Some(offset) = mixed {
+            return Err(invalid_character(offset));
+        }
+    }
+
+    let separator = text.rfind('1').ok_or(Invalid::Hrp)?;
+    let (prefix, rest) = text.split_at(separator);
+    if !prefix.eq_ignore_ascii_case(hrp) {
+        return Err(Invalid::Hrp);
+    }
+    if rest.len() < 7 {
+        return Err(Invalid::NoChecksum);
+    }
+
+    let mut data = Vec::with_capacity(rest.len() - 1);
+    for (i, c) in rest[1..].bytes().enumerate() {
+        let c = c.to_ascii_lowercase();
+        let Some((value, _)) = (0..).zip(CHARSET).find(|&(_, &a)| a == c) else {
+            return Err(invalid_character(separator + 1 + i));
+        };
+        data.push(value);
+    }
+    if polymod(hrp_expand(hrp).chain(data.iter().copied())) != constant {
+        return Err(Invalid::Checksum);
+    }
+    data.truncate(data.len() - 6);
+
+    // Join the 5-bit groups back into bytes. At most 4 bits of zero padding
+    // may be left over.
+    let mut bytes = Vec::with_capacity(data.len() * 5 / 8);
+    let mut acc = 0u16;
+    let mut bits = 0;
+    for value in data {
+        acc = (acc << 5 | u16::from(value)) & 0x0fff;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+        }
+    }
+    if bits >= 5 || acc & ((1 << bits) - 1) != 0 {
+        // The last character before the checksum holds the padding.
+        let offset = text.len() - 7;
+        return Err(Invalid::Decode(DecodeError::non_canonical(offset)));
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,564 @@
+//! Bit vectors packed 8 bits to a byte.
+//!
+//! Serde writes a `Vec<bool>` one element per bit. [`BitBuf`] and
+//! [`BitArray`] instead serialize as a 2-tuple of the number of bits and a
+//! byte string holding the bits packed 8 to a byte, the last byte padded with
+//! zero bits. The order of bits within each byte is chosen by a [`BitOrder`]
+//! type parameter, [`Msb0`] by default.
+//!
+//! The functions in this module do the same for a `Vec<bool>` field, in
+//! [`Msb0`] order. The [`lsb0`] submodule uses [`Lsb0`] order.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Features {
+//!     #[serde(with = "serde_bytes::bits")]
+//!     flags: Vec<bool>,
+//! }
+//!
+//! let features = Features {
+//!     flags: vec![true, false, true, true, false, false, false, false, true],
+//! };
+//! let json = serde_json::to_string(&features).unwrap();
+//! assert_eq!(json, r#"{"flags":[9,[176,128]]}"#);
+//! ```
+//!
+//! Deserialization fails if the byte string is not exactly long enough for
+//! the number of bits, or if a padding bit is set.
+
+use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Unexpected, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+use crate::{ByteBuf, Bytes};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Serde `serialize_with` function to serialize bools as packed bits in
+/// [`Msb0`] order.
+pub fn serialize<S>(bits: &[bool], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serialize_packed(bits.len(), &pack::<Msb0>(bits.iter().copied()), serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize bools from packed bits
+/// in [`Msb0`] order.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    BitBuf::<Msb0>::deserialize(deserializer).map(|bits| bits.iter().collect())
+}
+
+/// Packed bits in [`Lsb0`] order.
+pub mod lsb0 {
+    use super::{pack, serialize_packed, BitBuf, Lsb0};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[cfg(feature = "alloc")]
+    use alloc::vec::Vec;
+
+    /// Serde `serialize_with` function to serialize bools as packed bits in
+    /// [`Lsb0`] order.
+    pub fn serialize<S>(bits: &[bool], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_packed(bits.len(), &pack::<Lsb0>(bits.iter().copied()), serializer)
+    }
+
+    /// Serde `deserialize_with` function to deserialize bools from packed
+    /// bits in [`Lsb0`] order.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<bool>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        BitBuf::<Lsb0>::deserialize(deserializer).map(|bits| bits.iter().collect())
+    }
+}
+
+/// Order of the bits within each byte.
+pub trait BitOrder {
+    /// The mask selecting bit `index % 8` within its byte.
+    fn mask(index: usize) -> u8;
+}
+
+/// The first bit is the most significant bit of the byte.
+pub enum Msb0 {}
+
+impl BitOrder for Msb0 {
+    fn mask(index: usize) -> u8 {
+        0x80 >> (index % 8)
+    }
+}
+
+/// The first bit is the least significant bit of the byte.
+pub enum Lsb0 {}
+
+impl BitOrder for Lsb0 {
+    fn mask(index: usize) -> u8 {
+        1 << (index % 8)
+    }
+}
+
+/// Growable bit vector stored 8 bits to a byte.
+///
+/// ```
+/// use serde_bytes::bits::{BitBuf, Lsb0};
+///
+/// let mut flags = BitBuf::<Lsb0>::new();
+/// flags.push(true);
+/// flags.push(false);
+/// flags.push(true);
+/// assert_eq!(flags.len(), 3);
+/// assert_eq!(flags.as_bytes(), [0b101]);
+/// ```
+pub struct BitBuf<O: BitOrder = Msb0> {
+    bytes: Vec<u8>,
+    len: usize,
+    order: PhantomData<O>,
+}
+
+impl<O: BitOrder> BitBuf<O> {
+    /// Construct a new, empty `BitBuf`.
+    pub fn new() -> Self {
+        BitBuf {
+            bytes: Vec::new(),
+            len: 0,
+            order: PhantomData,
+        }
+    }
+
+    /// Number of bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The bit at `index`, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        if index < self.len {
+            Some(self.bytes[index / 8] & O::mask(index) != 0)
+        } else {
+            None
+        }
+    }
+
+    /// Set the bit at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, bit: bool) {
+        assert!(
+            index < self.len,
+            "index {index} out of range for BitBuf of length {}",
+            self.len,
+        );
+        if bit {
+            self.bytes[index / 8] |= O::mask(index);
+        } else {
+            self.bytes[index / 8] &= !O::mask(index);
+        }
+    }
+
+    /// Append a bit to the end.
+    pub fn push(&mut self, bit: bool) {
+        if self.len % 8 == 0 {
+            self.bytes.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, bit);
+    }
+
+    /// Iterate over the bits in order.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(move |index| self.bytes[index / 8] & O::mask(index) != 0)
+    }
+
+    /// The packed bytes, with the unused bits of the last byte zero.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<O: BitOrder> Clone for BitBuf<O> {
+    fn clone(&self) -> Self {
+        BitBuf {
+            bytes: self.bytes.clone(),
+            len: self.len,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<O: BitOrder> Default for BitBuf<O> {
+    fn default() -> Self {
+        BitBuf::new()
+    }
+}
+
+impl<O: BitOrder> Debug for BitBuf<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<O: BitOrder> PartialEq for BitBuf<O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.bytes == other.bytes
+    }
+}
+
+impl<O: BitOrder> Eq for BitBuf<O> {}
+
+impl<O: BitOrder> Hash for BitBuf<O> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        self.bytes.hash(state);
+    }
+}
+
+impl<O: BitOrder> FromIterator<bool> for BitBuf<O> {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bits = BitBuf::new();
+        for bit in iter {
+            bits.push(bit);
+        }
+        bits
+    }
+}
+
+impl<O: BitOrder> From<&[bool]> for BitBuf<O> {
+    fn from(bits: &[bool]) -> Self {
+        bits.iter().copied().collect()
+    }
+}
+
+impl<O: BitOrder> From<Vec<bool>> for BitBuf<O> {
+    fn from(bits: Vec<bool>) -> Self {
+        bits.into_iter().collect()
+    }
+}
+
+impl<O: BitOrder> From<BitBuf<O>> for Vec<bool> {
+    fn from(bits: BitBuf<O>) -> Self {
+        bits.iter().collect()
+    }
+}
+
+impl<O: BitOrder> Serialize for BitBuf<O> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_packed(self.len, &self.bytes, serializer)
+    }
+}
+
+impl<'de, O: BitOrder> Deserialize<'de> for BitBuf<O> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (len, bytes) = deserialize_packed::<O, D>(deserializer, None)?;
+        Ok(BitBuf {
+            bytes,
+            len,
+            order: PhantomData,
+        })
+    }
+}
+
+/// Fixed number of bits stored 8 bits to a byte.
+///
+/// `BYTES` must be the number of bytes holding `BITS` bits, rounded up,
+/// because stable Rust cannot compute it from `BITS`; any other value fails
+/// to compile.
+///
+/// ```
+/// use serde_bytes::BitArray;
+///
+/// let mut flags = BitArray::<9, 2>::new();
+/// flags.set(0, true);
+/// flags.set(8, true);
+/// assert_eq!(flags.get(8), Some(true));
+/// assert_eq!(flags.as_bytes(), &[0x80, 0x80]);
+/// ```
+///
+/// ```compile_fail
+/// // 9 bits do not fit in 1 byte.
+/// let flags = serde_bytes::BitArray::<9, 1>::new();
+/// ```
+pub struct BitArray<const BITS: usize, const BYTES: usize, O: BitOrder = Msb0> {
+    bytes: [u8; BYTES],
+    order: PhantomData<O>,
+}
+
+impl<const BITS: usize, const BYTES: usize, O: BitOrder> BitArray<BITS, BYTES, O> {
+    const CHECK_BYTES: () = assert!(
+        BYTES == BITS / 8 + if BITS % 8 == 0 { 0 } else { 1 },
+        "BitArray<BITS, BYTES> needs BYTES to be the number of bytes holding BITS bits",
+    );
+
+    /// Construct a `BitArray` with every bit unset.
+    pub const fn new() -> Self {
+        let () = Self::CHECK_BYTES;
+        BitArray {
+            bytes: [0; BYTES],
+            order: PhantomData,
+        }
+    }
+
+    /// Number of bits.
+    pub const fn len(&self) -> usize {
+        BITS
+    }
+
+    /// Whether there are no bits.
+    pub const fn is_empty(&self) -> bool {
+        BITS == 0
+    }
+
+    /// The bit at `index`, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        if index < BITS {
+            Some(self.bytes[index / 8] & O::mask(index) != 0)
+        } else {
+            None
+        }
+    }
+
+    /// Set the bit at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, bit: bool) {
+        assert!(
+            index < BITS,
+            "index {index} out of range for BitArray of length {BITS}",
+        );
+        if bit {
+            self.bytes[index / 8] |= O::mask(index);
+        } else {
+            self.bytes[index / 8] &= !O::mask(index);
+        }
+    }
+
+    /// Iterate over the bits in order.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..BITS).map(move |index| self.bytes[index / 8] & O::mask(index) != 0)
+    }
+
+    /// The packed bytes, with the unused bits of the last byte zero.
+    pub const fn as_bytes(&self) -> &[u8; BYTES] {
+        &self.bytes
+    }
+}
+
+impl<const BITS: usize, const BYTES: usize, O: BitOrder> Copy for BitArray<BITS, BYTES, O> {}
+
+impl<const BITS: usize, const BYTES: usize, O: BitOrder> Clone for BitArray<BITS, BYTES, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const BITS: usize, const BYTES: usize, O: BitOrder> Default for BitArray<BITS, BYTES, O> {
+    fn default() -> Self {
+        BitArray::new()
+    }
+}
+
+impl<const BITS: usize, const BYTES: usize, O: BitOrder> Debug for BitArray<BITS, BYTES, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<const BITS: usize, const BYTES: usize, O: BitOrder> PartialEq for BitArray<BITS, BYTES, O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl<const BITS: usize, const BYTES: usize, O: BitOrder> Eq for BitArray<BITS, BYTES, O> {}
+
+impl<const BITS: usize, const BYTES: usize, O: BitOrder> Hash for BitArray<BITS, BYTES, O> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state);
+    }
+}
+
+impl<const BITS: usize, const BYTES: usize, O: BitOrder> From<[bool; BITS]>
+    for BitArray<BITS, BYTES, O>
+{
+    fn from(bits: [bool; BITS]) -> Self {
+        let mut array = BitArray::new();
+        for (index, bit) in bits.into_iter().enumerate() {
+            array.set(index, bit);
+        }
+        array
+    }
+}
+
+impl<const BITS: usize, const BYTES: usize, O: BitOrder> From<BitArray<BITS, BYTES, O>>
+    for [bool; BITS]
+{
+    fn from(array: BitArray<BITS, BYTES, O>) -> Self {
+        let mut bits = [false; BITS];
+        for (slot, bit) in bits.iter_mut().zip(array.iter()) {
+            *slot = bit;
+        }
+        bits
+    }
+}
+
+impl<const BITS: usize, const BYTES: usize, O: BitOrder> Serialize for BitArray<BITS, BYTES, O> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_packed(BITS, &self.bytes, serializer)
+    }
+}
+
+impl<'de, const BITS: usize, const BYTES: usize, O: BitOrder> Deserialize<'de>
+    for BitArray<BITS, BYTES, O>
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (_, bytes) = deserialize_packed::<O, D>(deserializer, Some(BITS))?;
+        let mut array = BitArray::new();
+        array.bytes.copy_from_slice(&bytes);
+        Ok(array)
+    }
+}
+
+fn pack<O: BitOrder>(bits: impl ExactSizeIterator<Item = bool>) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(byte_len(bits.len()));
+    for (index, bit) in bits.enumerate() {
+        if index % 8 == 0 {
+            bytes.push(0);
+        }
+        if bit {
+            *bytes.last_mut().unwrap() |= O::mask(index);
+        }
+    }
+    bytes
+}
+
+// Number of bytes holding `bits` bits. The bit length may come from the
+// input, so this must not overflow the way `(bits + 7) / 8` would.
+fn byte_len(bits: usize) -> usize {
+    bits / 8 + usize::from(bits % 8 != 0)
+}
+
+fn serialize_packed<S>(len: usize, bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut tuple = serializer.serialize_tuple(2)?;
+    tuple.serialize_element(&(len as u64))?;
+    tuple.serialize_element(Bytes::new(bytes))?;
+    tuple.end()
+}
+
+// Reads the bit length and packed bytes, checking that there are exactly
+// enough bytes and that the padding bits are zero. If `bits` is given, the
+// bit length must equal it.
+fn deserialize_packed<'de, O, D>(
+    deserializer: D,
+    bits: Option<usize>,
+) -> Result<(usize, Vec<u8>), D::Error>
+where
+    O: BitOrder,
+    D: Deserializer<'de>,
+{
+    struct PackedVisitor<O> {
+        bits: Option<usize>,
+        order: PhantomData<O>,
+    }
+
+    impl<'de, O: BitOrder> Visitor<'de> for PackedVisitor<O> {
+        type Value = (usize, Vec<u8>);
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            match self.bits {
+                Some(bits) => write!(formatter, "a bit length of {bits} and packed bytes"),
+                None => formatter.write_str("a bit length and packed bytes"),
+            }
+        }
+
+        fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+        where
+            V: SeqAccess<'de>,
+        {
+            let len: u64 = seq
+                .next_element()?
+                .ok_or_else(|| V::Error::invalid_length(0, &self))?;
+            let len = match (usize::try_from(len), self.bits) {
+                (Ok(len), None) => len,
+                (Ok(len), Some(bits)) if len == bits => len,
+                _ => return Err(V::Error::invalid_value(Unexpected::Unsigned(len), &self)),
+            };
+            let bytes: ByteBuf = seq
+                .next_element()?
+                .ok_or_else(|| V::Error::invalid_length(1, &self))?;
+            let bytes = bytes.into_vec();
+
+            if bytes.len() != byte_len(len) {
+                return Err(V::Error::invalid_length(
+                    bytes.len(),
+                    &PackedBytes { bits: len },
+                ));
+            }
+            if let Some(&last) = bytes.last() {
+                let used = (len - 1) % 8 + 1;
+                let padding = (used..8).fold(0, |mask, i| mask | O::mask(i));
+                if last & padding != 0 {
+                    return Err(V::Error::invalid_value(
+                        Unexpected::Unsigned(u64::from(last)),
+                        &"a last byte with zero padding bits",
+                    ));
+                }
+            }
+            Ok((len, bytes))
+        }
+    }
+
+    deserializer.deserialize_tuple(
+        2,
+        PackedVisitor::<O> {
+            bits,
+            order: PhantomData,
+        },
+    )
+}
+
+struct PackedBytes {
+    bits: usize,
+}
+
+impl serde::de::Expected for PackedBytes {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let bits = self.bits;
+        write!(formatter, "{} packed bytes for {bits} bits", byte_len(bits))
+    }
+}
@@ -1,11 +1,19 @@
 use crate::Bytes;
 use core::borrow::{Borrow, BorrowMut};
 use core::cmp::Ordering;
-use core::fmt::{self, Debug};
+use core::fmt::{self, Debug, Display};
 use core::hash::{Hash, Hasher};
 use core::ops::{Deref, DerefMut};
 use core::ptr;
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::ByteBuf;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
 use serde::ser::{Serialize, Serializer};
 
@@ -52,7 +60,47 @@ impl<const N: usize> ByteArray<N> {
         self.bytes
     }
 
-    fn from_ref(bytes: &[u8; N]) -> &Self {
+    /// View the bytes as a slice.
+    pub const fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Parse `2 * N` hexadecimal digits, upper or lower case, into a
+    /// `ByteArray`. This is meant for constants.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hex` is not exactly `2 * N` hex digits. In a `const` item
+    /// this is a compile error.
+    ///
+    /// ```
+    /// use serde_bytes::ByteArray;
+    ///
+    /// const MAGIC: ByteArray<4> = ByteArray::from_hex("CAFEbabe");
+    /// assert_eq!(MAGIC, [0xca, 0xfe, 0xba, 0xbe]);
+    /// ```
+    pub const fn from_hex(hex: &str) -> Self {
+        const fn digit(c: u8) -> u8 {
+            match c {
+                b'0'..=b'9' => c - b'0',
+                b'a'..=b'f' => c - b'a' + 10,
+                b'A'..=b'F' => c - b'A' + 10,
+                _ => panic!("invalid hex digit"),
+            }
+        }
+
+        let hex = hex.as_bytes();
+        assert!(hex.len() == 2 * N, "wrong number of hex digits");
+        let mut bytes = [0; N];
+        let mut i = 0;
+        while i < N {
+            bytes[i] = digit(hex[2 * i]) << 4 | digit(hex[2 * i + 1]);
+            i += 1;
+        }
+        ByteArray { bytes }
+    }
+
+    pub(crate) fn from_ref(bytes: &[u8; N]) -> &Self {
         unsafe { &*ptr::addr_of!(*bytes).cast::<ByteArray<N>>() }
     }
 }
@@ -125,6 +173,98 @@ impl<const N: usize> From<[u8; N]> for ByteArray<N> {
     }
 }
 
+impl<const N: usize> From<ByteArray<N>> for [u8; N] {
+    fn from(bytes: ByteArray<N>) -> Self {
+        bytes.bytes
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const N: usize> From<ByteArray<N>> for ByteBuf {
+    fn from(bytes: ByteArray<N>) -> Self {
+        ByteBuf::from(bytes.bytes)
+    }
+}
+
+impl<'a, const N: usize> TryFrom<&'a [u8]> for ByteArray<N> {
+    type Error = LengthMismatch;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, LengthMismatch> {
+        match bytes.try_into() {
+            Ok(bytes) => Ok(ByteArray { bytes }),
+            Err(_) => Err(LengthMismatch::new(N, bytes.len())),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const N: usize> TryFrom<Vec<u8>> for ByteArray<N> {
+    type Error = LengthMismatch;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, LengthMismatch> {
+        ByteArray::try_from(&bytes[..])
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const N: usize> TryFrom<ByteBuf> for ByteArray<N> {
+    type Error = LengthMismatch;
+
+    fn try_from(bytes: ByteBuf) -> Result<Self, LengthMismatch> {
+        ByteArray::try_from(&bytes[..])
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const N: usize> From<Box<[u8; N]>> for Box<ByteArray<N>> {
+    fn from(bytes: Box<[u8; N]>) -> Self {
+        unsafe { Box::from_raw(Box::into_raw(bytes).cast::<ByteArray<N>>()) }
+    }
+}
+
+/// Error converting bytes of the wrong length into a [`ByteArray`].
+///
+/// ```
+/// use serde_bytes::ByteArray;
+///
+/// let err = ByteArray::<4>::try_from(&b"abc"[..]).unwrap_err();
+/// assert_eq!((err.expected(), err.actual()), (4, 3));
+/// assert_eq!(err.to_string(), "expected 4 bytes, found 3");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LengthMismatch {
+    expected: usize,
+    actual: usize,
+}
+
+impl LengthMismatch {
+    fn new(expected: usize, actual: usize) -> Self {
+        LengthMismatch { expected, actual }
+    }
+
+    /// The length of the `ByteArray`.
+    pub fn expected(&self) -> usize {
+        self.expected
+    }
+
+    /// The length of the bytes that were given.
+    pub fn actual(&self) -> usize {
+        self.actual
+    }
+}
+
+impl Display for LengthMismatch {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "expected {} bytes, found {}",
+            self.expected, self.actual,
+        )
+    }
+}
+
+impl serde::de::StdError for LengthMismatch {}
+
 impl<Rhs, const N: usize> PartialEq<Rhs> for ByteArray<N>
 where
     Rhs: ?Sized + Borrow<[u8; N]>,
@@ -185,7 +325,7 @@ impl<const N: usize> Serialize for ByteArray<N> {
     }
 }
 
-struct ByteArrayVisitor<const N: usize>;
+pub(crate) struct ByteArrayVisitor<const N: usize>;
 
 impl<'de, const N: usize> Visitor<'de> for ByteArrayVisitor<N> {
     type Value = ByteArray<N>;
@@ -262,6 +402,39 @@ impl<'de, const N: usize> Visitor<'de> for BorrowedByteArrayVisitor<N> {
     {
         self.visit_borrowed_bytes(v.as_bytes())
     }
+
+    // Bytes that only live as long as the call, such as from a reader or an
+    // escaped JSON string, cannot be returned as a reference.
+    fn visit_bytes<E>(self, _v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Err(cannot_borrow::<E, N>())
+    }
+
+    fn visit_str<E>(self, _v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Err(cannot_borrow::<E, N>())
+    }
+
+    fn visit_seq<V>(self, _seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        Err(cannot_borrow::<V::Error, N>())
+    }
+}
+
+fn cannot_borrow<E, const N: usize>() -> E
+where
+    E: Error,
+{
+    E::custom(format_args!(
+        "cannot borrow a byte array of length {N} from this input; \
+         use MaybeBorrowed or ByteArray to copy it instead",
+    ))
 }
 
 impl<'a, 'de: 'a, const N: usize> Deserialize<'de> for &'a ByteArray<N> {
@@ -0,0 +1,180 @@
+use crate::de::Deserialize as BytesDeserialize;
+use crate::msgpack::{AsBytes, BytesSeed};
+use crate::ser::Serialize as BytesSerialize;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{
+    Deserialize, Deserializer, EnumAccess, Error, SeqAccess, Unexpected, VariantAccess, Visitor,
+};
+use serde::ser::{Serialize, SerializeTupleVariant, Serializer};
+
+// Enum through which ciborium reads and writes CBOR tags.
+const TAG_ENUM: &str = "@@TAG@@";
+const UNTAGGED: &str = "@@UNTAGGED@@";
+const TAGGED: &str = "@@TAGGED@@";
+const VARIANTS: &[&str] = &[UNTAGGED, TAGGED];
+
+/// CBOR byte string with a fixed semantic tag.
+///
+/// Wraps any byte type supported by `serde_bytes`, such as `ByteBuf`,
+/// `&Bytes` or `ByteArray<N>`, and serializes it as a byte string preceded by
+/// CBOR tag `TAG` using the convention understood by ciborium, for example
+/// tag 2 for an unsigned bignum or tag 24 for embedded CBOR. Deserialization
+/// fails if the tag is missing or different.
+///
+/// Human-readable formats have no notion of CBOR tags, so there the tag is
+/// neither written nor expected and the value is handled like the underlying
+/// bytes. Other binary formats represent the tag as an enum variant holding
+/// the tag number and the bytes.
+///
+/// ```
+/// # use serde_derive::{Deserialize, Serialize};
+/// use serde::{Deserialize, Serialize};
+/// use serde_bytes::{ByteBuf, Tagged};
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Signed {
+///     // Tag 24: embedded CBOR data item.
+///     payload: Tagged<24, ByteBuf>,
+///     // Tag 2: unsigned bignum.
+///     serial: Tagged<2, ByteBuf>,
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tagged<const TAG: u64, T>(pub T);
+
+impl<const TAG: u64, T> Tagged<TAG, T> {
+    /// The CBOR tag number.
+    pub const TAG: u64 = TAG;
+
+    /// Unwrap the bytes underlying this tagged value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<const TAG: u64, T> Serialize for Tagged<TAG, T>
+where
+    T: BytesSerialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            return self.0.serialize(serializer);
+        }
+
+        let mut variant = serializer.serialize_tuple_variant(TAG_ENUM, 1, TAGGED, 2)?;
+        variant.serialize_field(&TAG)?;
+        variant.serialize_field(&AsBytes(&self.0))?;
+        variant.end()
+    }
+}
+
+struct TaggedVisitor<const TAG: u64, T> {
+    out: PhantomData<T>,
+}
+
+impl<'de, const TAG: u64, T> Visitor<'de> for TaggedVisitor<TAG, T>
+where
+    T: BytesDeserialize<'de>,
+{
+    type Value = Tagged<TAG, T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "byte string with CBOR tag {TAG}")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        match data.variant()? {
+            (Variant::Tagged, variant) => variant.tuple_variant(2, self),
+            (Variant::Untagged, _) => Err(A::Error::invalid_value(
+                Unexpected::Other("untagged value"),
+                &self,
+            )),
+        }
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let tag: u64 = seq
+            .next_element()?
+            .ok_or_else(|| V::Error::invalid_length(0, &self))?;
+        if tag != TAG {
+            return Err(V::Error::invalid_value(Unexpected::Unsigned(tag), &self));
+        }
+        let bytes = seq
+            .next_element_seed(BytesSeed { out: PhantomData })?
+            .ok_or_else(|| V::Error::invalid_length(1, &self))?;
+        Ok(Tagged(bytes))
+    }
+}
+
+enum Variant {
+    Untagged,
+    Tagged,
+}
+
+impl<'de> Deserialize<'de> for Variant {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct VariantVisitor;
+
+        impl<'de> Visitor<'de> for VariantVisitor {
+            type Value = Variant;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("CBOR tag variant")
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Variant, E>
+            where
+                E: Error,
+            {
+                match v {
+                    0 => Ok(Variant::Untagged),
+                    1 => Ok(Variant::Tagged),
+                    _ => Err(E::invalid_value(Unexpected::Unsigned(v), &self)),
+                }
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Variant, E>
+            where
+                E: Error,
+            {
+                match v {
+                    UNTAGGED => Ok(Variant::Untagged),
+                    TAGGED => Ok(Variant::Tagged),
+                    _ => Err(E::unknown_variant(v, VARIANTS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(VariantVisitor)
+    }
+}
+
+impl<'de, const TAG: u64, T> Deserialize<'de> for Tagged<TAG, T>
+where
+    T: BytesDeserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            return T::deserialize(deserializer).map(Tagged);
+        }
+
+        let visitor = TaggedVisitor { out: PhantomData };
+        deserializer.deserialize_enum(TAG_ENUM, VARIANTS, visitor)
+    }
+}
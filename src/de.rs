@@ -1,11 +1,14 @@
-use crate::{ByteArray, Bytes};
+use crate::aligned::{Align, Alignment};
+use crate::{AlignedByteArray, ByteArray, Bytes, MaybeBorrowed};
 use core::fmt;
 use core::marker::PhantomData;
-use serde::de::{Error, Visitor};
+use serde::de::{Error, Unexpected, Visitor};
 use serde::Deserializer;
 
 #[cfg(any(feature = "std", feature = "alloc"))]
-use crate::ByteBuf;
+use crate::bytearray::ByteArrayVisitor;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::{AlignedByteBuf, ByteBuf};
 
 #[cfg(any(feature = "std", feature = "alloc"))]
 use core::cmp;
@@ -22,6 +25,11 @@ use alloc::string::String;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+#[cfg(all(feature = "std", not(feature = "alloc")))]
+use std::collections::VecDeque;
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 use serde::de::SeqAccess;
 
@@ -103,6 +111,16 @@ impl<'de: 'a, 'a, const N: usize> Deserialize<'de> for &'a ByteArray<N> {
     }
 }
 
+impl<'de: 'a, 'a, const N: usize> Deserialize<'de> for MaybeBorrowed<'a, N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Via the serde::Deserialize impl for MaybeBorrowed.
+        serde::Deserialize::deserialize(deserializer)
+    }
+}
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 impl<'de> Deserialize<'de> for ByteBuf {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -114,6 +132,33 @@ impl<'de> Deserialize<'de> for ByteBuf {
     }
 }
 
+impl<'de, const N: usize, const ALIGN: usize> Deserialize<'de> for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Via the serde::Deserialize impl for AlignedByteArray.
+        serde::Deserialize::deserialize(deserializer)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de, const ALIGN: usize> Deserialize<'de> for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Via the serde::Deserialize impl for AlignedByteBuf.
+        serde::Deserialize::deserialize(deserializer)
+    }
+}
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 impl<'de: 'a, 'a> Deserialize<'de> for Cow<'a, [u8]> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -204,6 +249,78 @@ impl<'de: 'a, 'a> Deserialize<'de> for Cow<'a, Bytes> {
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de: 'a, 'a, const N: usize> Deserialize<'de> for Cow<'a, [u8; N]> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let cow: Cow<ByteArray<N>> = Deserialize::deserialize(deserializer)?;
+        match cow {
+            Cow::Borrowed(array) => Ok(Cow::Borrowed(array)),
+            Cow::Owned(array) => Ok(Cow::Owned(array.into_array())),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de: 'a, 'a, const N: usize> Deserialize<'de> for Cow<'a, ByteArray<N>> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct CowVisitor<const N: usize>;
+
+        impl<'de, const N: usize> Visitor<'de> for CowVisitor<N> {
+            type Value = Cow<'de, ByteArray<N>>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a byte array of length {N}")
+            }
+
+            fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                let array: &'de [u8; N] = v
+                    .try_into()
+                    .map_err(|_| E::invalid_length(v.len(), &self))?;
+                Ok(Cow::Borrowed(ByteArray::from_ref(array)))
+            }
+
+            fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                self.visit_borrowed_bytes(v.as_bytes())
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                ByteArrayVisitor::<N>.visit_bytes(v).map(Cow::Owned)
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                self.visit_bytes(v.as_bytes())
+            }
+
+            fn visit_seq<V>(self, visitor: V) -> Result<Self::Value, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                ByteArrayVisitor::<N>.visit_seq(visitor).map(Cow::Owned)
+            }
+        }
+
+        deserializer.deserialize_bytes(CowVisitor::<N>)
+    }
+}
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 impl<'de> Deserialize<'de> for Box<[u8]> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -225,6 +342,142 @@ impl<'de> Deserialize<'de> for Box<Bytes> {
     }
 }
 
+// Fills a heap allocation of length N instead of building the array on the
+// stack and moving it, which can overflow the stack for large N.
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de, const N: usize> Deserialize<'de> for Box<[u8; N]> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BoxedArrayVisitor<const N: usize>;
+
+        impl<const N: usize> BoxedArrayVisitor<N> {
+            fn boxed<E>(self, v: Vec<u8>) -> Result<Box<[u8; N]>, E>
+            where
+                E: Error,
+            {
+                let len = v.len();
+                v.into_boxed_slice()
+                    .try_into()
+                    .map_err(|_| E::invalid_length(len, &self))
+            }
+        }
+
+        impl<'de, const N: usize> Visitor<'de> for BoxedArrayVisitor<N> {
+            type Value = Box<[u8; N]>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a byte array of length {N}")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let mut bytes = Vec::with_capacity(N);
+
+                while bytes.len() < N {
+                    match visitor.next_element()? {
+                        Some(b) => bytes.push(b),
+                        None => return Err(V::Error::invalid_length(bytes.len(), &self)),
+                    }
+                }
+
+                self.boxed(bytes)
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                if v.len() != N {
+                    return Err(E::invalid_length(v.len(), &self));
+                }
+                self.boxed(v.to_vec())
+            }
+
+            fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                self.boxed(v)
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                self.visit_bytes(v.as_bytes())
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                self.boxed(v.into_bytes())
+            }
+        }
+
+        deserializer.deserialize_bytes(BoxedArrayVisitor::<N>)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de, const N: usize> Deserialize<'de> for Box<ByteArray<N>> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes: Box<[u8; N]> = Deserialize::deserialize(deserializer)?;
+        Ok(bytes.into())
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de> Deserialize<'de> for VecDeque<u8> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer).map(Vec::into)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for &'a str {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes: &[u8] = Deserialize::deserialize(deserializer)?;
+        core::str::from_utf8(bytes)
+            .map_err(|_| D::Error::invalid_value(Unexpected::Bytes(bytes), &"UTF-8 bytes"))
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de> Deserialize<'de> for String {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes: Vec<u8> = Deserialize::deserialize(deserializer)?;
+        String::from_utf8(bytes).map_err(|err| {
+            D::Error::invalid_value(Unexpected::Bytes(err.as_bytes()), &"UTF-8 bytes")
+        })
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de> Deserialize<'de> for Box<str> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer).map(String::into_boxed_str)
+    }
+}
+
 impl<'de, T> Deserialize<'de> for Option<T>
 where
     T: Deserialize<'de>,
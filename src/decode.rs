@@ -0,0 +1,158 @@
+use core::fmt;
+use serde::de::{self, Unexpected};
+
+#[cfg(feature = "alloc")]
+use alloc::format;
+
+/// Error from decoding text with an [`Encoding`](crate::adaptive::Encoding).
+///
+/// When deserializing, an invalid character or non-canonical text becomes
+/// [`de::Error::invalid_value`] and an invalid length becomes
+/// [`de::Error::invalid_length`], with a message naming the encoding and the
+/// offset.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodeError {
+    kind: DecodeErrorKind,
+    offset: usize,
+    character: Option<char>,
+    expected_length: Option<&'static str>,
+}
+
+/// What is wrong with text that failed to decode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DecodeErrorKind {
+    /// A character that is not valid at its position.
+    InvalidCharacter,
+    /// Text that ends in the middle of a group of characters.
+    InvalidLength,
+    /// Text that decodes to bytes, but is not what encoding those bytes
+    /// produces, such as Base64 with nonzero unused bits.
+    NonCanonical,
+}
+
+impl DecodeError {
+    /// The character starting at byte `offset` of `text` is not valid there.
+    ///
+    /// If `offset` is inside a character, the error points at the start of
+    /// that character.
+    pub fn invalid_character(text: &str, offset: usize) -> Self {
+        let offset = (0..=offset.min(text.len()))
+            .rev()
+            .find(|&i| text.is_char_boundary(i))
+            .unwrap_or(0);
+        DecodeError {
+            kind: DecodeErrorKind::InvalidCharacter,
+            offset,
+            character: text[offset..].chars().next(),
+            expected_length: None,
+        }
+    }
+
+    /// Text of `length` bytes cannot be decoded. `expected` describes the
+    /// valid lengths, such as `"a multiple of 2"`.
+    pub fn invalid_length(length: usize, expected: &'static str) -> Self {
+        DecodeError {
+            kind: DecodeErrorKind::InvalidLength,
+            offset: length,
+            character: None,
+            expected_length: Some(expected),
+        }
+    }
+
+    /// The text is not in canonical form from byte `offset` on.
+    pub fn non_canonical(offset: usize) -> Self {
+        DecodeError {
+            kind: DecodeErrorKind::NonCanonical,
+            offset,
+            character: None,
+            expected_length: None,
+        }
+    }
+
+    /// What is wrong with the text.
+    pub fn kind(&self) -> DecodeErrorKind {
+        self.kind
+    }
+
+    /// Byte offset into the text where the problem is. For an invalid
+    /// length this is the length of the text.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The invalid character, for [`DecodeErrorKind::InvalidCharacter`].
+    pub fn character(&self) -> Option<char> {
+        self.character
+    }
+
+    /// Description of the valid lengths, for
+    /// [`DecodeErrorKind::InvalidLength`].
+    pub fn expected_length(&self) -> Option<&'static str> {
+        self.expected_length
+    }
+
+    // Offsets into text that follows a prefix of `len` bytes. An invalid
+    // length stays the length of the text after the prefix.
+    pub(crate) fn after_prefix(mut self, len: usize) -> Self {
+        if self.kind != DecodeErrorKind::InvalidLength {
+            self.offset += len;
+        }
+        self
+    }
+
+    // Maps the error to a deserialization error for `text`, the whole string
+    // that was being decoded as `name`.
+    pub(crate) fn into_de_error<E>(self, name: &str, text: &str) -> E
+    where
+        E: de::Error,
+    {
+        let offset = self.offset;
+        match self.kind {
+            DecodeErrorKind::InvalidCharacter => {
+                let unexpected = match self.character {
+                    Some(c) => Unexpected::Char(c),
+                    None => Unexpected::Str(text),
+                };
+                let expected = format!("a valid {name} character at offset {offset}");
+                E::invalid_value(unexpected, &expected.as_str())
+            }
+            DecodeErrorKind::InvalidLength => {
+                let expected = match self.expected_length {
+                    Some(expected) => format!("a {name} string whose length is {expected}"),
+                    None => format!("a {name} string"),
+                };
+                E::invalid_length(offset, &expected.as_str())
+            }
+            DecodeErrorKind::NonCanonical => {
+                let expected = format!("canonical {name} at offset {offset}");
+                E::invalid_value(Unexpected::Str(text), &expected.as_str())
+            }
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let offset = self.offset;
+        match (self.kind, self.character, self.expected_length) {
+            (DecodeErrorKind::InvalidCharacter, Some(c), _) => {
+                write!(formatter, "invalid character {c:?} at offset {offset}")
+            }
+            (DecodeErrorKind::InvalidCharacter, None, _) => {
+                write!(formatter, "invalid character at offset {offset}")
+            }
+            (DecodeErrorKind::InvalidLength, _, Some(expected)) => {
+                write!(formatter, "invalid length {offset}, expected {expected}")
+            }
+            (DecodeErrorKind::InvalidLength, _, None) => {
+                write!(formatter, "invalid length {offset}")
+            }
+            (DecodeErrorKind::NonCanonical, _, _) => {
+                write!(formatter, "non-canonical encoding at offset {offset}")
+            }
+        }
+    }
+}
+
+impl de::StdError for DecodeError {}
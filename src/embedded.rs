@@ -0,0 +1,193 @@
+use crate::ByteBuf;
+use core::fmt::{self, Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+/// Encoding used for the inner value of an [`Embedded`].
+///
+/// ```
+/// use serde_bytes::{ByteBuf, Codec};
+///
+/// struct Bincode;
+///
+/// impl<T> Codec<T> for Bincode
+/// where
+///     T: serde::Serialize + serde::de::DeserializeOwned,
+/// {
+///     type Error = String;
+///
+///     fn encode(value: &T) -> Result<ByteBuf, Self::Error> {
+///         bincode::serde::encode_to_vec(value, bincode::config::standard())
+///             .map(ByteBuf::from)
+///             .map_err(|err| err.to_string())
+///     }
+///
+///     fn decode(bytes: &[u8]) -> Result<T, Self::Error> {
+///         bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+///             .map(|(value, _len)| value)
+///             .map_err(|err| err.to_string())
+///     }
+/// }
+/// ```
+pub trait Codec<T> {
+    /// Error produced when the inner value cannot be encoded or decoded.
+    type Error: Display;
+
+    /// Encode `value` into bytes.
+    fn encode(value: &T) -> Result<ByteBuf, Self::Error>;
+
+    /// Decode a value from `bytes`.
+    fn decode(bytes: &[u8]) -> Result<T, Self::Error>;
+}
+
+/// Value of type `T` serialized as a byte string holding its encoding under
+/// codec `C`.
+///
+/// The bytes are kept as they were received and only decoded on request,
+/// which allows checking a signature over the exact encoded bytes before
+/// trusting their content.
+///
+/// ```
+/// # use serde_derive::{Deserialize, Serialize};
+/// use serde::{Deserialize, Serialize};
+/// use serde_bytes::Embedded;
+/// #
+/// # use serde_bytes::{ByteBuf, Codec};
+/// #
+/// # struct Bincode;
+/// #
+/// # impl<T> Codec<T> for Bincode
+/// # where
+/// #     T: serde::Serialize + serde::de::DeserializeOwned,
+/// # {
+/// #     type Error = String;
+/// #
+/// #     fn encode(value: &T) -> Result<ByteBuf, Self::Error> {
+/// #         bincode::serde::encode_to_vec(value, bincode::config::standard())
+/// #             .map(ByteBuf::from)
+/// #             .map_err(|err| err.to_string())
+/// #     }
+/// #
+/// #     fn decode(bytes: &[u8]) -> Result<T, Self::Error> {
+/// #         bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+/// #             .map(|(value, _len)| value)
+/// #             .map_err(|err| err.to_string())
+/// #     }
+/// # }
+/// #
+/// # fn verify(_data: &[u8], _signature: &[u8]) -> bool {
+/// #     true
+/// # }
+///
+/// #[derive(Deserialize, Serialize, PartialEq, Debug)]
+/// struct Inner {
+///     id: u32,
+///     name: String,
+/// }
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Envelope {
+///     inner: Embedded<Inner, Bincode>,
+///     #[serde(with = "serde_bytes")]
+///     signature: Vec<u8>,
+/// }
+///
+/// fn open(envelope: &Envelope) -> Option<Inner> {
+///     if !verify(envelope.inner.raw(), &envelope.signature) {
+///         return None;
+///     }
+///     envelope.inner.decode().ok()
+/// }
+/// #
+/// # let inner = Inner { id: 1, name: "one".to_owned() };
+/// # let envelope = Envelope {
+/// #     inner: Embedded::encode(&inner).unwrap(),
+/// #     signature: Vec::new(),
+/// # };
+/// # assert_eq!(open(&envelope), Some(inner));
+/// ```
+pub struct Embedded<T, C> {
+    bytes: ByteBuf,
+    marker: PhantomData<fn() -> (T, C)>,
+}
+
+impl<T, C> Embedded<T, C>
+where
+    C: Codec<T>,
+{
+    /// Encode `value` with the codec `C`.
+    pub fn encode(value: &T) -> Result<Self, C::Error> {
+        C::encode(value).map(Embedded::from_raw)
+    }
+
+    /// Decode the embedded value with the codec `C`.
+    pub fn decode(&self) -> Result<T, C::Error> {
+        C::decode(&self.bytes)
+    }
+}
+
+impl<T, C> Embedded<T, C> {
+    /// Wrap bytes that are already encoded, without checking that they decode.
+    pub fn from_raw(bytes: ByteBuf) -> Self {
+        Embedded {
+            bytes,
+            marker: PhantomData,
+        }
+    }
+
+    /// The encoded bytes of the embedded value.
+    pub fn raw(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Unwrap the encoded bytes of the embedded value.
+    pub fn into_raw(self) -> ByteBuf {
+        self.bytes
+    }
+}
+
+impl<T, C> Clone for Embedded<T, C> {
+    fn clone(&self) -> Self {
+        Embedded::from_raw(self.bytes.clone())
+    }
+}
+
+impl<T, C> Debug for Embedded<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Embedded").field(&self.bytes).finish()
+    }
+}
+
+impl<T, C> PartialEq for Embedded<T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl<T, C> Eq for Embedded<T, C> {}
+
+impl<T, C> Hash for Embedded<T, C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state);
+    }
+}
+
+impl<T, C> Serialize for Embedded<T, C> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.bytes)
+    }
+}
+
+impl<'de, T, C> Deserialize<'de> for Embedded<T, C> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ByteBuf::deserialize(deserializer).map(Embedded::from_raw)
+    }
+}
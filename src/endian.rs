@@ -0,0 +1,327 @@
+//! Element types and containers for the [`le`](crate::le) and
+//! [`be`](crate::be) modules.
+
+use core::mem;
+use serde::de::{Deserializer, Error};
+use serde::ser::Serializer;
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(all(feature = "std", not(feature = "alloc")))]
+use std::borrow::Cow;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::format;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// A number with a fixed size in bytes, written in either byte order.
+///
+/// Implemented for the primitive integer types and for `f32` and `f64`. The
+/// one-byte `u8` and `i8` are the same in either order, and are included so
+/// that any integer element type works.
+pub trait Element: Copy + Default {
+    /// Number of bytes in one element.
+    const SIZE: usize;
+
+    /// Append the little-endian bytes of `self` to `out`.
+    fn extend_le(self, out: &mut Vec<u8>);
+
+    /// Append the big-endian bytes of `self` to `out`.
+    fn extend_be(self, out: &mut Vec<u8>);
+
+    /// Read an element from exactly `SIZE` little-endian bytes.
+    fn from_le(bytes: &[u8]) -> Self;
+
+    /// Read an element from exactly `SIZE` big-endian bytes.
+    fn from_be(bytes: &[u8]) -> Self;
+}
+
+macro_rules! element {
+    ($($ty:ty)*) => {
+        $(
+            impl Element for $ty {
+                const SIZE: usize = mem::size_of::<$ty>();
+
+                fn extend_le(self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn extend_be(self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_be_bytes());
+                }
+
+                fn from_le(bytes: &[u8]) -> Self {
+                    let mut array = [0; mem::size_of::<$ty>()];
+                    array.copy_from_slice(bytes);
+                    <$ty>::from_le_bytes(array)
+                }
+
+                fn from_be(bytes: &[u8]) -> Self {
+                    let mut array = [0; mem::size_of::<$ty>()];
+                    array.copy_from_slice(bytes);
+                    <$ty>::from_be_bytes(array)
+                }
+            }
+        )*
+    };
+}
+
+element!(u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64);
+
+/// Types that can be serialized with the [`le`](crate::le) and
+/// [`be`](crate::be) modules.
+pub trait Elements {
+    /// The number type held.
+    type Element: Element;
+
+    /// The elements in order.
+    fn elements(&self) -> &[Self::Element];
+}
+
+impl<E: Element> Elements for [E] {
+    type Element = E;
+
+    fn elements(&self) -> &[E] {
+        self
+    }
+}
+
+impl<E: Element, const N: usize> Elements for [E; N] {
+    type Element = E;
+
+    fn elements(&self) -> &[E] {
+        self
+    }
+}
+
+impl<E: Element> Elements for Vec<E> {
+    type Element = E;
+
+    fn elements(&self) -> &[E] {
+        self
+    }
+}
+
+impl<E: Element> Elements for Box<[E]> {
+    type Element = E;
+
+    fn elements(&self) -> &[E] {
+        self
+    }
+}
+
+impl<T> Elements for &T
+where
+    T: ?Sized + Elements,
+{
+    type Element = T::Element;
+
+    fn elements(&self) -> &[T::Element] {
+        (**self).elements()
+    }
+}
+
+/// Types that can be deserialized with the [`le`](crate::le) and
+/// [`be`](crate::be) modules.
+pub trait FromElements: Sized {
+    /// The number type held.
+    type Element: Element;
+
+    /// The required number of elements, if fixed.
+    const LEN: Option<usize> = None;
+
+    /// Collect the decoded elements. There are exactly `LEN` of them if
+    /// `LEN` is set.
+    fn from_elements<I>(elements: I) -> Self
+    where
+        I: ExactSizeIterator<Item = Self::Element>;
+}
+
+impl<E: Element, const N: usize> FromElements for [E; N] {
+    type Element = E;
+
+    const LEN: Option<usize> = Some(N);
+
+    fn from_elements<I>(elements: I) -> Self
+    where
+        I: ExactSizeIterator<Item = E>,
+    {
+        let mut array = [E::default(); N];
+        for (slot, element) in array.iter_mut().zip(elements) {
+            *slot = element;
+        }
+        array
+    }
+}
+
+impl<E: Element> FromElements for Vec<E> {
+    type Element = E;
+
+    fn from_elements<I>(elements: I) -> Self
+    where
+        I: ExactSizeIterator<Item = E>,
+    {
+        elements.collect()
+    }
+}
+
+impl<E: Element> FromElements for Box<[E]> {
+    type Element = E;
+
+    fn from_elements<I>(elements: I) -> Self
+    where
+        I: ExactSizeIterator<Item = E>,
+    {
+        elements.collect()
+    }
+}
+
+pub(crate) trait ByteOrder {
+    fn extend<E: Element>(element: E, out: &mut Vec<u8>);
+    fn read<E: Element>(bytes: &[u8]) -> E;
+}
+
+pub(crate) enum LittleEndian {}
+
+impl ByteOrder for LittleEndian {
+    fn extend<E: Element>(element: E, out: &mut Vec<u8>) {
+        element.extend_le(out);
+    }
+
+    fn read<E: Element>(bytes: &[u8]) -> E {
+        E::from_le(bytes)
+    }
+}
+
+pub(crate) enum BigEndian {}
+
+impl ByteOrder for BigEndian {
+    fn extend<E: Element>(element: E, out: &mut Vec<u8>) {
+        element.extend_be(out);
+    }
+
+    fn read<E: Element>(bytes: &[u8]) -> E {
+        E::from_be(bytes)
+    }
+}
+
+pub(crate) fn serialize<O, T, S>(elements: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    O: ByteOrder,
+    T: ?Sized + Elements,
+    S: Serializer,
+{
+    let elements = elements.elements();
+    let mut bytes = Vec::with_capacity(elements.len() * T::Element::SIZE);
+    for &element in elements {
+        O::extend(element, &mut bytes);
+    }
+    serializer.serialize_bytes(&bytes)
+}
+
+pub(crate) fn deserialize<'de, O, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    O: ByteOrder,
+    T: FromElements,
+    D: Deserializer<'de>,
+{
+    let size = T::Element::SIZE;
+    let bytes: Cow<[u8]> = crate::Deserialize::deserialize(deserializer)?;
+    let len = bytes.len();
+    match T::LEN {
+        Some(n) if len != n * size => {
+            let expected = format!("a byte string of length {}", n * size);
+            return Err(D::Error::invalid_length(len, &expected.as_str()));
+        }
+        None if len % size != 0 => {
+            let expected = format!("a byte string whose length is a multiple of {size}");
+            return Err(D::Error::invalid_length(len, &expected.as_str()));
+        }
+        _ => {}
+    }
+    Ok(T::from_elements(bytes.chunks_exact(size).map(O::read)))
+}
+
+// Defines the `le` and `be` modules, which differ only in byte order.
+macro_rules! byte_order_module {
+    ($module:ident, $order:ident, $order_name:literal, $other:ident, $json:literal) => {
+        #[doc = concat!(
+                    "Slices and arrays of numbers as one byte string in ",
+                    $order_name,
+                    " order.\n",
+                    "\n",
+                    "Serde writes a `Vec<u16>` or `[f32; 3]` one element at a time. This\n",
+                    "module instead writes the elements back to back in ",
+                    $order_name,
+                    " byte order\n",
+                    "as a single byte string, which formats with native byte strings store\n",
+                    "compactly. The [`",
+                    stringify!($other),
+                    "`](crate::",
+                    stringify!($other),
+                    ") module does the same in the opposite order.\n",
+                    "\n",
+                    "Deserialization fails if the length of the byte string is not a multiple\n",
+                    "of the element size, or for an array, not exactly the size of the array.\n",
+                    "Supported element types are listed under [`Element`].\n",
+                    "\n",
+                    "```\n",
+                    "# use serde_derive::{Deserialize, Serialize};\n",
+                    "use serde::{Deserialize, Serialize};\n",
+                    "\n",
+                    "#[derive(Deserialize, Serialize)]\n",
+                    "struct Samples {\n",
+                    "    #[serde(with = \"serde_bytes::",
+                    stringify!($module),
+                    "\")]\n",
+                    "    values: Vec<u16>,\n",
+                    "}\n",
+                    "\n",
+                    "let samples = Samples {\n",
+                    "    values: vec![1, 256],\n",
+                    "};\n",
+                    "let json = serde_json::to_string(&samples).unwrap();\n",
+                    "assert_eq!(json, r#\"{\"values\":",
+                    $json,
+                    "}\"#);\n",
+                    "```\n",
+                    "\n",
+                    "[`Element`]: crate::endian::Element",
+                )]
+        pub mod $module {
+            use crate::endian::{self, $order, Elements, FromElements};
+            use serde::{Deserializer, Serializer};
+
+            #[doc = concat!(
+                        "Serde `serialize_with` function to serialize numbers as ",
+                        $order_name,
+                        "\nbytes.",
+                    )]
+            pub fn serialize<T, S>(elements: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: ?Sized + Elements,
+                S: Serializer,
+            {
+                endian::serialize::<$order, T, S>(elements, serializer)
+            }
+
+            #[doc = concat!(
+                        "Serde `deserialize_with` function to deserialize numbers from ",
+                        $order_name,
+                        "\nbytes.",
+                    )]
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+            where
+                T: FromElements,
+                D: Deserializer<'de>,
+            {
+                endian::deserialize::<$order, T, D>(deserializer)
+            }
+        }
+    };
+}
+
+pub(crate) use byte_order_module;
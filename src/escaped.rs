@@ -0,0 +1,207 @@
+//! Escaped text in human-readable formats, a native byte string otherwise.
+//!
+//! Bytes that are mostly printable ASCII, like HTTP header values or keys in
+//! a key-value store, stay readable: printable ASCII characters are written
+//! as themselves and every other byte as an escape sequence. The functions at
+//! the top of this module write `\xNN` with lowercase hex digits and `\\` for
+//! a backslash. The [`percent`] submodule writes `%NN` with uppercase digits.
+//!
+//! Deserialization only accepts the text that serializing the same bytes
+//! would produce. An escape of a character that is written as itself, such
+//! as `\x41` for `A`, is an error just like a malformed escape.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Entry {
+//!     #[serde(with = "serde_bytes::escaped")]
+//!     key: Vec<u8>,
+//!     #[serde(with = "serde_bytes::escaped::percent")]
+//!     value: Vec<u8>,
+//! }
+//!
+//! let entry = Entry {
+//!     key: b"user:\x00\x2a".to_vec(),
+//!     value: b"50% off\n".to_vec(),
+//! };
+//! let json = serde_json::to_string(&entry).unwrap();
+//! assert_eq!(json, r#"{"key":"user:\\x00*","value":"50%25 off%0A"}"#);
+//! ```
+
+use crate::adaptive::{Adaptive, DecodeError, Encoding};
+use crate::de::Deserialize;
+use crate::ser::Serialize;
+use serde::{Deserializer, Serializer};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Serde `serialize_with` function to serialize bytes with `\xNN` escapes.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Adaptive::<Backslash>::serialize(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize bytes with `\xNN`
+/// escapes.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Adaptive::<Backslash>::deserialize(deserializer)
+}
+
+/// Percent-encoding as in URLs, with `%NN` escapes.
+pub mod percent {
+    use super::Percent;
+    use crate::adaptive::Adaptive;
+    use crate::de::Deserialize;
+    use crate::ser::Serialize;
+    use serde::{Deserializer, Serializer};
+
+    /// Serde `serialize_with` function to serialize bytes with `%NN`
+    /// escapes.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        Adaptive::<Percent>::serialize(bytes, serializer)
+    }
+
+    /// Serde `deserialize_with` function to deserialize bytes with `%NN`
+    /// escapes.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Adaptive::<Percent>::deserialize(deserializer)
+    }
+}
+
+/// Printable ASCII as itself, `\\` for a backslash and `\xNN` with lowercase
+/// hex digits for any other byte.
+pub enum Backslash {}
+
+impl Encoding for Backslash {
+    const NAME: &'static str = "backslash-escaped";
+
+    fn encode(bytes: &[u8]) -> String {
+        let mut text = String::with_capacity(bytes.len());
+        for &byte in bytes {
+            match byte {
+                b'\\' => text.push_str("\\\\"),
+                b' '..=b'~' => text.push(char::from(byte)),
+                _ => {
+                    text.push_str("\\x");
+                    push_hex(&mut text, byte, b"0123456789abcdef");
+                }
+            }
+        }
+        text
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        let chars = text.as_bytes();
+        let mut bytes = Vec::with_capacity(text.len());
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                b'\\' => match chars.get(i + 1) {
+                    Some(b'\\') => {
+                        bytes.push(b'\\');
+                        i += 2;
+                    }
+                    Some(b'x') => {
+                        let byte = hex_byte(text, i, i + 2, b'a')?;
+                        if (b' '..=b'~').contains(&byte) {
+                            return Err(DecodeError::non_canonical(i));
+                        }
+                        bytes.push(byte);
+                        i += 4;
+                    }
+                    Some(_) => return Err(DecodeError::invalid_character(text, i + 1)),
+                    None => return Err(DecodeError::invalid_character(text, i)),
+                },
+                c @ b' '..=b'~' => {
+                    bytes.push(c);
+                    i += 1;
+                }
+                _ => return Err(DecodeError::invalid_character(text, i)),
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+/// Printable ASCII other than `%` as itself, and `%NN` with uppercase hex
+/// digits for any other byte.
+pub enum Percent {}
+
+impl Encoding for Percent {
+    const NAME: &'static str = "percent-encoded";
+
+    fn encode(bytes: &[u8]) -> String {
+        let mut text = String::with_capacity(bytes.len());
+        for &byte in bytes {
+            if byte != b'%' && (b' '..=b'~').contains(&byte) {
+                text.push(char::from(byte));
+            } else {
+                text.push('%');
+                push_hex(&mut text, byte, b"0123456789ABCDEF");
+            }
+        }
+        text
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        let chars = text.as_bytes();
+        let mut bytes = Vec::with_capacity(text.len());
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                b'%' => {
+                    let byte = hex_byte(text, i, i + 1, b'A')?;
+                    if byte != b'%' && (b' '..=b'~').contains(&byte) {
+                        return Err(DecodeError::non_canonical(i));
+                    }
+                    bytes.push(byte);
+                    i += 3;
+                }
+                c @ b' '..=b'~' => {
+                    bytes.push(c);
+                    i += 1;
+                }
+                _ => return Err(DecodeError::invalid_character(text, i)),
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+fn push_hex(text: &mut String, byte: u8, digits: &[u8; 16]) {
+    text.push(char::from(digits[usize::from(byte >> 4)]));
+    text.push(char::from(digits[usize::from(byte & 0xf)]));
+}
+
+// Two hex digits at `at`, in the escape that starts at `escape`. Only digits
+// in the case the encoder writes are accepted, `a` or `A` being the letter
+// for 10. An escape cut short by the end of the text is reported at its start.
+fn hex_byte(text: &str, escape: usize, at: usize, ten: u8) -> Result<u8, DecodeError> {
+    let digit = |i: usize| match text.as_bytes().get(i) {
+        Some(&c @ b'0'..=b'9') => Ok(c - b'0'),
+        Some(&c) if (ten..ten + 6).contains(&c) => Ok(c - ten + 10),
+        Some(_) => Err(DecodeError::invalid_character(text, i)),
+        None => Err(DecodeError::invalid_character(text, escape)),
+    };
+    Ok(digit(at)? << 4 | digit(at + 1)?)
+}
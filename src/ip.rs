@@ -0,0 +1,139 @@
+//! IP addresses as bytes in binary formats, text in human-readable formats.
+//!
+//! Serde writes an `Ipv4Addr` or `Ipv6Addr` as a string even in binary
+//! formats, and an `IpAddr` as an enum around it. This module writes the
+//! 4 or 16 octets of the address as a byte string in binary formats, and the
+//! usual text form such as `192.0.2.1` or `2001:db8::1` in human-readable
+//! formats.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//! use std::net::{IpAddr, Ipv4Addr};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Peer {
+//!     #[serde(with = "serde_bytes::ip")]
+//!     addr: Ipv4Addr,
+//!     #[serde(with = "serde_bytes::ip")]
+//!     gateway: IpAddr,
+//! }
+//!
+//! let peer = Peer {
+//!     addr: Ipv4Addr::new(192, 0, 2, 1),
+//!     gateway: "2001:db8::1".parse().unwrap(),
+//! };
+//! let json = serde_json::to_string(&peer).unwrap();
+//! assert_eq!(json, r#"{"addr":"192.0.2.1","gateway":"2001:db8::1"}"#);
+//! ```
+//!
+//! An `IpAddr` is deserialized from either 4 or 16 bytes.
+
+use crate::address::{self, Address};
+use crate::{ByteArray, ByteBuf};
+use core::fmt::{self, Display};
+use serde::de::{Deserializer, Error};
+use serde::ser::Serializer;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Serde `serialize_with` function to serialize an IP address as bytes or
+/// text.
+pub fn serialize<T, S>(address: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: IpAddress,
+    S: Serializer,
+{
+    address::serialize(address, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize an IP address from bytes
+/// or text.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: IpAddress,
+    D: Deserializer<'de>,
+{
+    address::deserialize(deserializer)
+}
+
+/// `Ipv4Addr`, `Ipv6Addr` or `IpAddr`.
+pub trait IpAddress: Address {}
+
+impl IpAddress for Ipv4Addr {}
+impl IpAddress for Ipv6Addr {}
+impl IpAddress for IpAddr {}
+
+impl Address for Ipv4Addr {
+    type Octets = ByteArray<4>;
+
+    const EXPECTING: &'static str = "an IPv4 address";
+
+    fn octets(&self) -> Self::Octets {
+        ByteArray::new(Ipv4Addr::octets(self))
+    }
+
+    fn from_octets<E: Error>(octets: Self::Octets) -> Result<Self, E> {
+        Ok(Ipv4Addr::from(octets.into_array()))
+    }
+
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(self, formatter)
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+}
+
+impl Address for Ipv6Addr {
+    type Octets = ByteArray<16>;
+
+    const EXPECTING: &'static str = "an IPv6 address";
+
+    fn octets(&self) -> Self::Octets {
+        ByteArray::new(Ipv6Addr::octets(self))
+    }
+
+    fn from_octets<E: Error>(octets: Self::Octets) -> Result<Self, E> {
+        Ok(Ipv6Addr::from(octets.into_array()))
+    }
+
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(self, formatter)
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+}
+
+impl Address for IpAddr {
+    type Octets = ByteBuf;
+
+    const EXPECTING: &'static str = "an IP address";
+
+    fn octets(&self) -> Self::Octets {
+        match self {
+            IpAddr::V4(addr) => ByteBuf::from(addr.octets()),
+            IpAddr::V6(addr) => ByteBuf::from(addr.octets()),
+        }
+    }
+
+    fn from_octets<E: Error>(octets: Self::Octets) -> Result<Self, E> {
+        if let Ok(octets) = <[u8; 4]>::try_from(&octets[..]) {
+            Ok(IpAddr::from(octets))
+        } else if let Ok(octets) = <[u8; 16]>::try_from(&octets[..]) {
+            Ok(IpAddr::from(octets))
+        } else {
+            Err(E::invalid_length(octets.len(), &"4 or 16 bytes"))
+        }
+    }
+
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(self, formatter)
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+}
@@ -0,0 +1,276 @@
+//! Deserialization that accepts bytes in any of several shapes.
+//!
+//! While producers of some data are migrating between formats, a reader may
+//! need to accept all of them. [`Lenient<W, A>`] accepts a native byte
+//! string, a sequence of `u8`, or a string in any of the encodings `A`, and
+//! always serializes with the single representation `W`.
+//!
+//! A string that starts with the [prefix] of one of the encodings, such as
+//! `0x` for hex, is decoded with that encoding only. Any other string is
+//! decoded with the first encoding of `A` that accepts it, so list the
+//! encodings from most to least specific: `"abcd"` is valid hex as well as
+//! valid Base64.
+//!
+//! [prefix]: crate::adaptive::Encoding::PREFIX
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//! use serde_bytes::adaptive::{Adaptive, Base64, Hex};
+//! use serde_bytes::lenient::Lenient;
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Record {
+//!     #[serde(with = "Lenient::<Adaptive<Hex>, (Hex, Base64)>")]
+//!     digest: Vec<u8>,
+//! }
+//!
+//! for json in [
+//!     r#"{"digest":"cafe"}"#,
+//!     r#"{"digest":"0xcafe"}"#,
+//!     r#"{"digest":"base64:yv4="}"#,
+//!     r#"{"digest":[202,254]}"#,
+//! ] {
+//!     let record: Record = serde_json::from_str(json).unwrap();
+//!     assert_eq!(record.digest, [0xca, 0xfe]);
+//!
+//!     let json = serde_json::to_string(&record).unwrap();
+//!     assert_eq!(json, r#"{"digest":"cafe"}"#);
+//! }
+//! ```
+
+use crate::adaptive::{Adaptive, Base64, Base64Url, DecodeError, Encoding, Hex, Representation};
+use crate::de::Deserialize;
+use crate::repr;
+use crate::ser::Serialize;
+use core::cmp;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, Deserializer, SeqAccess, Unexpected, Visitor};
+use serde::ser::Serializer;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Serde `serialize_with` function. Writes hex in human-readable formats and
+/// a native byte string otherwise.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Standard::serialize(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function. Accepts a native byte string, a
+/// sequence of `u8`, or a hex, Base64 or URL-safe Base64 string.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Standard::deserialize(deserializer)
+}
+
+type Standard = Lenient<Adaptive<Hex>, (Hex, Base64, Base64Url)>;
+
+/// Writes representation `W`, reads bytes, a `u8` sequence or a string in
+/// any of the encodings `A`.
+///
+/// Use it as `#[serde(with = "Lenient::<W, A>")]`, where `A` is a tuple of
+/// [`Encoding`] types.
+pub struct Lenient<W, A> {
+    write: PhantomData<W>,
+    accept: PhantomData<A>,
+}
+
+impl<W, A> Lenient<W, A>
+where
+    W: Representation,
+    A: Encodings,
+{
+    /// Serde `serialize_with` function.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        repr::serialize::<Self, T, S>(bytes, serializer)
+    }
+
+    /// Serde `deserialize_with` function.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        repr::deserialize::<Self, T, D>(deserializer)
+    }
+}
+
+impl<W, A> Representation for Lenient<W, A>
+where
+    W: Representation,
+    A: Encodings,
+{
+    fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        W::serialize(bytes, serializer)
+    }
+
+    fn deserialize<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Visitor<'de>,
+    {
+        let visitor = LenientVisitor::<A, V> {
+            visitor,
+            accept: PhantomData,
+        };
+        // Formats that are not self-describing cannot deserialize_any, and
+        // are not where mixed producers show up.
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(visitor)
+        } else {
+            W::deserialize(deserializer, visitor)
+        }
+    }
+}
+
+/// Set of encodings accepted by [`Lenient`], implemented for tuples of up
+/// to 6 [`Encoding`] types.
+pub trait Encodings {
+    /// Decode text with the encoding named by its prefix, or else with the
+    /// first encoding that accepts it.
+    ///
+    /// If a prefix selected the encoding and decoding fails, the error is
+    /// that encoding's [`DecodeError`], with offsets into the whole `text`,
+    /// along with the encoding's name. If no encoding accepts unprefixed
+    /// text, the error is `None`.
+    fn decode(text: &str) -> Result<Vec<u8>, Option<(&'static str, DecodeError)>>;
+
+    /// Write the names of the encodings for error messages, such as
+    /// `"hex or base64"`.
+    fn fmt_names(formatter: &mut fmt::Formatter) -> fmt::Result;
+}
+
+fn strip_prefix<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    if prefix.is_empty() {
+        None
+    } else {
+        text.strip_prefix(prefix)
+    }
+}
+
+macro_rules! tuple_impls {
+    ($($len:literal => ($($e:ident)+))+) => {
+        $(
+            impl<$($e),+> Encodings for ($($e,)+)
+            where
+                $($e: Encoding,)+
+            {
+                fn decode(text: &str) -> Result<Vec<u8>, Option<(&'static str, DecodeError)>> {
+                    $(
+                        if let Some(rest) = strip_prefix(text, $e::PREFIX) {
+                            return $e::decode(rest).map_err(|err| {
+                                Some(($e::NAME, err.after_prefix($e::PREFIX.len())))
+                            });
+                        }
+                    )+
+                    $(
+                        if let Ok(bytes) = $e::decode(text) {
+                            return Ok(bytes);
+                        }
+                    )+
+                    Err(None)
+                }
+
+                fn fmt_names(formatter: &mut fmt::Formatter) -> fmt::Result {
+                    let names = [$($e::NAME),+];
+                    for (i, name) in names.iter().enumerate() {
+                        if i > 0 {
+                            let sep = if i + 1 == $len { " or " } else { ", " };
+                            formatter.write_str(sep)?;
+                        }
+                        formatter.write_str(name)?;
+                    }
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+tuple_impls! {
+    1 => (E0)
+    2 => (E0 E1)
+    3 => (E0 E1 E2)
+    4 => (E0 E1 E2 E3)
+    5 => (E0 E1 E2 E3 E4)
+    6 => (E0 E1 E2 E3 E4 E5)
+}
+
+struct LenientVisitor<A, V> {
+    visitor: V,
+    accept: PhantomData<A>,
+}
+
+impl<'de, A, V> Visitor<'de> for LenientVisitor<A, V>
+where
+    A: Encodings,
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a byte string, a sequence of u8, or a ")?;
+        A::fmt_names(formatter)?;
+        formatter.write_str(" string")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visitor.visit_bytes(v)
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visitor.visit_borrowed_bytes(v)
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visitor.visit_byte_buf(v)
+    }
+
+    fn visit_seq<S>(self, mut seq: S) -> Result<V::Value, S::Error>
+    where
+        S: SeqAccess<'de>,
+    {
+        let len = cmp::min(seq.size_hint().unwrap_or(0), 4096);
+        let mut bytes = Vec::with_capacity(len);
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+        self.visitor.visit_byte_buf(bytes)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        match A::decode(v) {
+            Ok(bytes) => self.visitor.visit_byte_buf(bytes),
+            Err(Some((name, err))) => Err(err.into_de_error(name, v)),
+            Err(None) => Err(E::invalid_value(Unexpected::Str(v), &self)),
+        }
+    }
+}
@@ -44,27 +44,148 @@
 
 extern crate serde_core as serde;
 
+mod address;
 mod bytearray;
 mod bytes;
+mod cbor;
 mod de;
+mod maybeborrowed;
+mod msgpack;
 mod ser;
 
+#[cfg(feature = "bytemuck")]
+mod pod;
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 mod bytebuf;
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod decode;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod embedded;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod repr;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod sha256;
+
+pub mod aligned;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod adaptive;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod ascii85;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod audit;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod base32;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod base58;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod base58check;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+endian::byte_order_module!(be, BigEndian, "big-endian", le, "[0,1,1,0]");
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod bech32;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod bits;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod endian;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod escaped;
+
+#[cfg(feature = "std")]
+pub mod ip;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+endian::byte_order_module!(le, LittleEndian, "little-endian", be, "[1,0,0,1]");
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod lenient;
+
+pub mod mac;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod packed;
+
+#[cfg(feature = "uuid")]
+pub mod uuid;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod z85;
+
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
 use serde::{Deserializer, Serializer};
 
-pub use crate::bytearray::ByteArray;
+pub use crate::aligned::AlignedByteArray;
+pub use crate::bytearray::{ByteArray, LengthMismatch};
 pub use crate::bytes::Bytes;
+pub use crate::cbor::Tagged;
 pub use crate::de::Deserialize;
+pub use crate::maybeborrowed::MaybeBorrowed;
+pub use crate::msgpack::MsgPackExt;
 pub use crate::ser::Serialize;
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use crate::aligned::AlignedByteBuf;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use crate::bits::{BitArray, BitBuf};
 #[cfg(any(feature = "std", feature = "alloc"))]
 pub use crate::bytebuf::ByteBuf;
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use crate::embedded::{Codec, Embedded};
+
+#[cfg(feature = "bytemuck")]
+pub use crate::pod::PodSlice;
+
+#[cfg(all(feature = "bytemuck", any(feature = "std", feature = "alloc")))]
+pub use crate::pod::PodCow;
+
+/// Attribute macro to apply `#[serde(with = "serde_bytes")]` to every byte
+/// field of a struct or enum.
+///
+/// Fields of type `Vec<u8>`, `[u8; N]`, `&[u8]`, `&[u8; N]`, `Box<[u8]>`,
+/// `Cow<[u8]>`, and `Option` of any of these are recognized syntactically.
+/// Fields that already have a `with`, `serialize_with` or `deserialize_with`
+/// attribute are left alone, and an individual field can be excluded with
+/// `#[serde_bytes(ignore)]`. `Cow` fields also get `#[serde(borrow)]` so
+/// that they borrow from the input when possible.
+///
+/// The attribute must be placed *above* the `#[derive]` so that it runs
+/// before Serde's derive macros see the struct.
+///
+/// ```
+/// # use serde_derive::{Deserialize, Serialize};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[serde_bytes::auto]
+/// #[derive(Deserialize, Serialize)]
+/// struct Packet {
+///     // Serialized as a byte string.
+///     payload: Vec<u8>,
+///     nonce: [u8; 12],
+///     signature: Option<Vec<u8>>,
+///
+///     // Serialized as a sequence of integers.
+///     #[serde_bytes(ignore)]
+///     histogram: Vec<u8>,
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use serde_bytes_derive::auto;
+
 /// Serde `serialize_with` function to serialize bytes efficiently.
 ///
 /// This function can be used with either of the following Serde attributes:
@@ -0,0 +1,90 @@
+//! MAC addresses as bytes in binary formats, text in human-readable formats.
+//!
+//! A MAC address held as `[u8; 6]` is written as a 6-byte string in binary
+//! formats and as six pairs of lowercase hex digits separated by colons in
+//! human-readable formats. Deserialization accepts hex digits in either case.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Interface {
+//!     #[serde(with = "serde_bytes::mac")]
+//!     hwaddr: [u8; 6],
+//! }
+//!
+//! let interface = Interface {
+//!     hwaddr: [0x00, 0x1b, 0x44, 0x11, 0x3a, 0xb7],
+//! };
+//! let json = serde_json::to_string(&interface).unwrap();
+//! assert_eq!(json, r#"{"hwaddr":"00:1b:44:11:3a:b7"}"#);
+//! ```
+
+use crate::address::{self, Address};
+use crate::ByteArray;
+use core::fmt;
+use serde::de::{Deserializer, Error};
+use serde::ser::Serializer;
+
+/// Serde `serialize_with` function to serialize a MAC address as bytes or
+/// text.
+pub fn serialize<S>(address: &[u8; 6], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    address::serialize(&Mac(*address), serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize a MAC address from bytes
+/// or text.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 6], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    address::deserialize(deserializer).map(|Mac(octets)| octets)
+}
+
+struct Mac([u8; 6]);
+
+impl Address for Mac {
+    type Octets = ByteArray<6>;
+
+    const EXPECTING: &'static str = "a MAC address";
+
+    fn octets(&self) -> Self::Octets {
+        ByteArray::new(self.0)
+    }
+
+    fn from_octets<E: Error>(octets: Self::Octets) -> Result<Self, E> {
+        Ok(Mac(octets.into_array()))
+    }
+
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, octet) in self.0.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(":")?;
+            }
+            write!(formatter, "{octet:02x}")?;
+        }
+        Ok(())
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.as_bytes();
+        if text.len() != 17 {
+            return None;
+        }
+        let mut octets = [0; 6];
+        for (i, octet) in octets.iter_mut().enumerate() {
+            if i > 0 && text[i * 3 - 1] != b':' {
+                return None;
+            }
+            let digit = |c: u8| char::from(c).to_digit(16);
+            let hi = digit(text[i * 3])?;
+            let lo = digit(text[i * 3 + 1])?;
+            *octet = u8::try_from(hi << 4 | lo).ok()?;
+        }
+        Some(Mac(octets))
+    }
+}
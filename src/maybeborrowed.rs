@@ -0,0 +1,212 @@
+use crate::bytearray::ByteArrayVisitor;
+use crate::{ByteArray, Bytes};
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// A `[u8; N]` borrowed from the input when possible, copied otherwise.
+///
+/// Deserializing `&[u8; N]` fails when the bytes do not outlive the
+/// deserializer, for example when reading from an `io::Read` or from a JSON
+/// string containing escapes. `MaybeBorrowed` borrows in the same cases that
+/// `&[u8; N]` would succeed and holds a copy of the array in the rest, without
+/// needing an allocator.
+///
+/// ```
+/// # use serde_derive::Deserialize;
+/// use serde::Deserialize;
+/// use serde_bytes::MaybeBorrowed;
+///
+/// #[derive(Deserialize)]
+/// struct Packet<'a> {
+///     #[serde(borrow)]
+///     id: MaybeBorrowed<'a, 4>,
+/// }
+///
+/// let packet: Packet = serde_json::from_str(r#"{"id":"abcd"}"#).unwrap();
+/// assert!(packet.id.is_borrowed());
+///
+/// let packet: Packet = serde_json::from_str(r#"{"id":"ab\u0063d"}"#).unwrap();
+/// assert!(!packet.id.is_borrowed());
+/// assert_eq!(packet.id, *b"abcd");
+/// ```
+#[derive(Copy, Clone)]
+pub enum MaybeBorrowed<'a, const N: usize> {
+    /// Array borrowed from the input.
+    Borrowed(&'a [u8; N]),
+    /// Copy of an array that could not be borrowed.
+    Owned([u8; N]),
+}
+
+impl<'a, const N: usize> MaybeBorrowed<'a, N> {
+    /// Whether the array is borrowed from the input.
+    pub const fn is_borrowed(&self) -> bool {
+        matches!(self, MaybeBorrowed::Borrowed(_))
+    }
+
+    /// Copy the array out, whether borrowed or owned.
+    pub const fn into_array(self) -> [u8; N] {
+        match self {
+            MaybeBorrowed::Borrowed(bytes) => *bytes,
+            MaybeBorrowed::Owned(bytes) => bytes,
+        }
+    }
+}
+
+impl<'a, const N: usize> Debug for MaybeBorrowed<'a, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<'a, const N: usize> AsRef<[u8; N]> for MaybeBorrowed<'a, N> {
+    fn as_ref(&self) -> &[u8; N] {
+        self
+    }
+}
+
+impl<'a, const N: usize> Borrow<[u8; N]> for MaybeBorrowed<'a, N> {
+    fn borrow(&self) -> &[u8; N] {
+        self
+    }
+}
+
+impl<'a, const N: usize> Borrow<Bytes> for MaybeBorrowed<'a, N> {
+    fn borrow(&self) -> &Bytes {
+        Bytes::new(&**self)
+    }
+}
+
+impl<'a, const N: usize> Deref for MaybeBorrowed<'a, N> {
+    type Target = [u8; N];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            MaybeBorrowed::Borrowed(bytes) => bytes,
+            MaybeBorrowed::Owned(bytes) => bytes,
+        }
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for MaybeBorrowed<'a, N> {
+    fn from(bytes: &'a [u8; N]) -> Self {
+        MaybeBorrowed::Borrowed(bytes)
+    }
+}
+
+impl<'a, const N: usize> From<[u8; N]> for MaybeBorrowed<'a, N> {
+    fn from(bytes: [u8; N]) -> Self {
+        MaybeBorrowed::Owned(bytes)
+    }
+}
+
+impl<'a, const N: usize> From<MaybeBorrowed<'a, N>> for ByteArray<N> {
+    fn from(bytes: MaybeBorrowed<'a, N>) -> Self {
+        ByteArray::new(bytes.into_array())
+    }
+}
+
+impl<'a, Rhs, const N: usize> PartialEq<Rhs> for MaybeBorrowed<'a, N>
+where
+    Rhs: ?Sized + Borrow<[u8; N]>,
+{
+    fn eq(&self, other: &Rhs) -> bool {
+        (**self).eq(other.borrow())
+    }
+}
+
+impl<'a, const N: usize> Eq for MaybeBorrowed<'a, N> {}
+
+impl<'a, Rhs, const N: usize> PartialOrd<Rhs> for MaybeBorrowed<'a, N>
+where
+    Rhs: ?Sized + Borrow<[u8; N]>,
+{
+    fn partial_cmp(&self, other: &Rhs) -> Option<Ordering> {
+        (**self).partial_cmp(other.borrow())
+    }
+}
+
+impl<'a, const N: usize> Ord for MaybeBorrowed<'a, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<'a, const N: usize> Hash for MaybeBorrowed<'a, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+impl<'a, const N: usize> Serialize for MaybeBorrowed<'a, N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&**self)
+    }
+}
+
+struct MaybeBorrowedVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for MaybeBorrowedVisitor<N> {
+    type Value = MaybeBorrowed<'de, N>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a byte array of length {N}")
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let bytes: &'de [u8; N] = v
+            .try_into()
+            .map_err(|_| E::invalid_length(v.len(), &self))?;
+        Ok(MaybeBorrowed::Borrowed(bytes))
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.visit_borrowed_bytes(v.as_bytes())
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let array = ByteArrayVisitor::<N>.visit_bytes(v)?;
+        Ok(MaybeBorrowed::Owned(array.into_array()))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.visit_bytes(v.as_bytes())
+    }
+
+    fn visit_seq<V>(self, seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let array = ByteArrayVisitor::<N>.visit_seq(seq)?;
+        Ok(MaybeBorrowed::Owned(array.into_array()))
+    }
+}
+
+impl<'a, 'de: 'a, const N: usize> Deserialize<'de> for MaybeBorrowed<'a, N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(MaybeBorrowedVisitor::<N>)
+    }
+}
@@ -0,0 +1,164 @@
+use crate::de::Deserialize as BytesDeserialize;
+use crate::ser::Serialize as BytesSerialize;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{
+    Deserialize, DeserializeSeed, Deserializer, Error, SeqAccess, Unexpected, Visitor,
+};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+// Name of the newtype struct through which rmp-serde reads and writes
+// MessagePack extension types.
+const EXT_STRUCT_NAME: &str = "_ExtStruct";
+
+/// `MessagePack` extension type with a fixed type code.
+///
+/// Wraps any byte type supported by `serde_bytes`, such as `ByteBuf`,
+/// `&Bytes` or `ByteArray<N>`, and serializes it as `MessagePack` extension
+/// `TAG` using the convention understood by rmp-serde. Deserialization fails
+/// if the input holds an extension with a different type code.
+///
+/// ```
+/// use serde_bytes::{ByteArray, MsgPackExt};
+///
+/// // Extension type -1 is the MessagePack timestamp.
+/// type Timestamp32 = MsgPackExt<-1, ByteArray<4>>;
+///
+/// let timestamp: Timestamp32 = MsgPackExt(ByteArray::new(1_700_000_000u32.to_be_bytes()));
+/// let encoded = rmp_serde::to_vec(&timestamp).unwrap();
+/// assert_eq!(encoded, [0xd6, 0xff, 0x65, 0x53, 0xf1, 0x00]);
+///
+/// let decoded: Timestamp32 = rmp_serde::from_slice(&encoded).unwrap();
+/// assert_eq!(decoded, timestamp);
+///
+/// assert!(rmp_serde::from_slice::<MsgPackExt<1, ByteArray<4>>>(&encoded).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MsgPackExt<const TAG: i8, T>(pub T);
+
+impl<const TAG: i8, T> MsgPackExt<TAG, T> {
+    /// The `MessagePack` extension type code.
+    pub const TAG: i8 = TAG;
+
+    /// Unwrap the bytes underlying this extension value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<const TAG: i8, T> Serialize for MsgPackExt<TAG, T>
+where
+    T: BytesSerialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct Ext<'a, T>(i8, &'a T);
+
+        impl<'a, T> Serialize for Ext<'a, T>
+        where
+            T: BytesSerialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                let mut tuple = serializer.serialize_tuple(2)?;
+                tuple.serialize_element(&self.0)?;
+                tuple.serialize_element(&AsBytes(self.1))?;
+                tuple.end()
+            }
+        }
+
+        serializer.serialize_newtype_struct(EXT_STRUCT_NAME, &Ext(TAG, &self.0))
+    }
+}
+
+struct ExtVisitor<const TAG: i8, T> {
+    out: PhantomData<T>,
+}
+
+impl<'de, const TAG: i8, T> Visitor<'de> for ExtVisitor<TAG, T>
+where
+    T: BytesDeserialize<'de>,
+{
+    type Value = MsgPackExt<TAG, T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "MessagePack extension type {TAG}")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let tag: i8 = seq
+            .next_element()?
+            .ok_or_else(|| V::Error::invalid_length(0, &self))?;
+        if tag != TAG {
+            return Err(V::Error::invalid_value(
+                Unexpected::Signed(i64::from(tag)),
+                &self,
+            ));
+        }
+        let bytes = seq
+            .next_element_seed(BytesSeed { out: PhantomData })?
+            .ok_or_else(|| V::Error::invalid_length(1, &self))?;
+        Ok(MsgPackExt(bytes))
+    }
+}
+
+// Serializes the payload through this crate's Serialize trait. Shared with
+// the CBOR Tagged wrapper.
+pub(crate) struct AsBytes<'a, T>(pub &'a T);
+
+impl<'a, T> Serialize for AsBytes<'a, T>
+where
+    T: BytesSerialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+pub(crate) struct BytesSeed<T> {
+    pub(crate) out: PhantomData<T>,
+}
+
+impl<'de, T> DeserializeSeed<'de> for BytesSeed<T>
+where
+    T: BytesDeserialize<'de>,
+{
+    type Value = T;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+impl<'de, const TAG: i8, T> Deserialize<'de> for MsgPackExt<TAG, T>
+where
+    T: BytesDeserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let visitor = ExtVisitor { out: PhantomData };
+        deserializer.deserialize_newtype_struct(EXT_STRUCT_NAME, visitor)
+    }
+}
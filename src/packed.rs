@@ -0,0 +1,239 @@
+//! Length-prefixed representation for formats without native byte strings.
+//!
+//! Formats such as JSON have no byte string type, so `#[serde(with =
+//! "serde_bytes")]` falls back to writing one number per byte. This module
+//! instead writes a struct holding the length in bytes and the data packed
+//! into big-endian `u64` words, the last one padded with zeros.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Record {
+//!     #[serde(with = "serde_bytes::packed")]
+//!     payload: Vec<u8>,
+//! }
+//! ```
+//!
+//! A `Record` with payload `[1, 2, 3]` is serialized to JSON as:
+//!
+//! ```json
+//! {"payload":{"len":3,"data":[72623842526232576]}}
+//! ```
+//!
+//! Deserialization also accepts a native byte string.
+
+use crate::de::Deserialize;
+use crate::repr::{self, Representation};
+use crate::ser::Serialize;
+use core::fmt;
+use serde::de::{self, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, SerializeSeq, SerializeStruct, Serializer};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+const NAME: &str = "PackedBytes";
+const FIELDS: &[&str] = &["len", "data"];
+
+/// Serde `serialize_with` function to serialize bytes as packed words.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    repr::serialize::<Packed, T, S>(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize bytes from packed words.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    repr::deserialize::<Packed, T, D>(deserializer)
+}
+
+enum Packed {}
+
+impl Representation for Packed {
+    fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(NAME, 2)?;
+        state.serialize_field("len", &bytes.len())?;
+        state.serialize_field("data", &Words(bytes))?;
+        state.end()
+    }
+
+    fn deserialize<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Visitor<'de>,
+    {
+        deserializer.deserialize_struct(NAME, FIELDS, PackedVisitor { visitor })
+    }
+}
+
+struct Words<'a>(&'a [u8]);
+
+impl<'a> ser::Serialize for Words<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some((self.0.len() + 7) / 8))?;
+        for chunk in self.0.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            seq.serialize_element(&u64::from_be_bytes(word))?;
+        }
+        seq.end()
+    }
+}
+
+struct PackedVisitor<V> {
+    visitor: V,
+}
+
+impl<V> PackedVisitor<V> {
+    fn unpack<E>(len: usize, words: &[u64]) -> Result<Vec<u8>, E>
+    where
+        E: de::Error,
+    {
+        // `len` comes from the input, so `len + 7` could overflow.
+        if words.len() != len / 8 + usize::from(len % 8 != 0) {
+            return Err(E::invalid_length(
+                words.len(),
+                &"one word per 8 bytes of `len`",
+            ));
+        }
+        let mut bytes = Vec::with_capacity(words.len() * 8);
+        for word in words {
+            bytes.extend_from_slice(&word.to_be_bytes());
+        }
+        if bytes[len..].iter().any(|&b| b != 0) {
+            return Err(E::invalid_value(
+                de::Unexpected::Other("nonzero padding"),
+                &"zero padding after the last byte",
+            ));
+        }
+        bytes.truncate(len);
+        Ok(bytes)
+    }
+}
+
+enum Field {
+    Len,
+    Data,
+    Other,
+}
+
+impl<'de> de::Deserialize<'de> for Field {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("`len` or `data`")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Field, E>
+            where
+                E: de::Error,
+            {
+                Ok(match v {
+                    "len" => Field::Len,
+                    "data" => Field::Data,
+                    _ => Field::Other,
+                })
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+impl<'de, V> Visitor<'de> for PackedVisitor<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("packed bytes")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<V::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let len: usize = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let words: Vec<u64> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let bytes = Self::unpack(len, &words)?;
+        self.visitor.visit_byte_buf(bytes)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<V::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut len = None;
+        let mut words = None;
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Len => {
+                    if len.is_some() {
+                        return Err(de::Error::duplicate_field("len"));
+                    }
+                    len = Some(map.next_value::<usize>()?);
+                }
+                Field::Data => {
+                    if words.is_some() {
+                        return Err(de::Error::duplicate_field("data"));
+                    }
+                    words = Some(map.next_value::<Vec<u64>>()?);
+                }
+                Field::Other => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        let len = len.ok_or_else(|| de::Error::missing_field("len"))?;
+        let words = words.ok_or_else(|| de::Error::missing_field("data"))?;
+        let bytes = Self::unpack(len, &words)?;
+        self.visitor.visit_byte_buf(bytes)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visitor.visit_bytes(v)
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visitor.visit_borrowed_bytes(v)
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visitor.visit_byte_buf(v)
+    }
+}
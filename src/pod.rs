@@ -0,0 +1,224 @@
+use bytemuck::{Pod, PodCastError};
+use core::fmt::{self, Debug};
+use core::mem;
+use core::ops::Deref;
+use serde::de::{Deserialize, Deserializer, Error, Expected};
+use serde::ser::{Serialize, Serializer};
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(all(feature = "std", not(feature = "alloc")))]
+use std::borrow::Cow;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Slice of [`Pod`] values borrowed directly from a byte string.
+///
+/// The values are written and read in native byte order, exactly as they are
+/// laid out in memory. Deserialization borrows from the input like `&[u8]`
+/// does, so it fails if the format cannot borrow, if the bytes are not
+/// aligned for `T`, or if their length is not a multiple of the size of `T`.
+/// [`PodCow`] copies the bytes instead when they cannot be borrowed or are
+/// misaligned.
+///
+/// ```
+/// use serde::de::value::{BorrowedBytesDeserializer, Error};
+/// use serde::Deserialize;
+/// use serde_bytes::PodSlice;
+///
+/// #[repr(C, align(4))]
+/// struct Aligned([u8; 8]);
+///
+/// let input = Aligned([1, 0, 0, 0, 2, 0, 0, 0]);
+/// let deserializer = BorrowedBytesDeserializer::<Error>::new(&input.0);
+/// let values = PodSlice::<u32>::deserialize(deserializer).unwrap();
+/// assert_eq!(*values, [u32::from_ne_bytes([1, 0, 0, 0]), u32::from_ne_bytes([2, 0, 0, 0])]);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PodSlice<'a, T> {
+    values: &'a [T],
+}
+
+impl<'a, T: Pod> PodSlice<'a, T> {
+    /// Wrap an existing slice into a `PodSlice`.
+    pub const fn new(values: &'a [T]) -> Self {
+        PodSlice { values }
+    }
+
+    /// Unwrap the slice underlying this `PodSlice`.
+    pub const fn into_slice(self) -> &'a [T] {
+        self.values
+    }
+}
+
+impl<'a, T: Debug> Debug for PodSlice<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(self.values, f)
+    }
+}
+
+impl<'a, T> Deref for PodSlice<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.values
+    }
+}
+
+impl<'a, T: Pod> From<&'a [T]> for PodSlice<'a, T> {
+    fn from(values: &'a [T]) -> Self {
+        PodSlice::new(values)
+    }
+}
+
+impl<'a, T: Pod> Serialize for PodSlice<'a, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(bytemuck::cast_slice(self.values))
+    }
+}
+
+impl<'a, 'de: 'a, T: Pod> Deserialize<'de> for PodSlice<'a, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes: &'de [u8] = crate::Deserialize::deserialize(deserializer)?;
+        match bytemuck::try_cast_slice(bytes) {
+            Ok(values) => Ok(PodSlice::new(values)),
+            Err(PodCastError::TargetAlignmentGreaterAndInputNotAligned) => {
+                Err(D::Error::custom(format_args!(
+                    "borrowed bytes are not aligned to {} bytes; use PodCow to copy them instead",
+                    mem::align_of::<T>(),
+                )))
+            }
+            Err(err) => Err(cast_error::<T, D::Error>(bytes.len(), err)),
+        }
+    }
+}
+
+/// Slice of [`Pod`] values borrowed from a byte string when possible, copied
+/// otherwise.
+///
+/// Like [`PodSlice`], the values are in native byte order. Deserialization
+/// borrows when the format provides bytes that outlive the deserializer and
+/// are aligned for `T`, and copies them into a `Vec<T>` in every other case.
+/// It fails only if the length is not a multiple of the size of `T`.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct PodCow<'a, T: Pod> {
+    values: Cow<'a, [T]>,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: Pod> PodCow<'a, T> {
+    /// Wrap an existing borrowed or owned slice into a `PodCow`.
+    pub fn new(values: Cow<'a, [T]>) -> Self {
+        PodCow { values }
+    }
+
+    /// Whether the values are borrowed from the input.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.values, Cow::Borrowed(_))
+    }
+
+    /// Unwrap the `Cow` underlying this `PodCow`.
+    pub fn into_cow(self) -> Cow<'a, [T]> {
+        self.values
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: Pod + Debug> Debug for PodCow<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&*self.values, f)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: Pod> Deref for PodCow<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.values
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: Pod> From<&'a [T]> for PodCow<'a, T> {
+    fn from(values: &'a [T]) -> Self {
+        PodCow::new(Cow::Borrowed(values))
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: Pod> From<Vec<T>> for PodCow<'a, T> {
+    fn from(values: Vec<T>) -> Self {
+        PodCow::new(Cow::Owned(values))
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: Pod> Serialize for PodCow<'a, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(bytemuck::cast_slice(&self.values))
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, 'de: 'a, T: Pod> Deserialize<'de> for PodCow<'a, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes: Cow<'de, [u8]> = crate::Deserialize::deserialize(deserializer)?;
+        if let Cow::Borrowed(bytes) = bytes {
+            if let Ok(values) = bytemuck::try_cast_slice(bytes) {
+                return Ok(PodCow::new(Cow::Borrowed(values)));
+            }
+        }
+
+        // A Vec<u8> cannot be reused because its allocation is only aligned
+        // for u8.
+        let size = mem::size_of::<T>();
+        if size == 0 || bytes.len() % size != 0 {
+            return Err(cast_error::<T, D::Error>(
+                bytes.len(),
+                PodCastError::OutputSliceWouldHaveSlop,
+            ));
+        }
+        let mut values = Vec::new();
+        values.resize(bytes.len() / size, T::zeroed());
+        bytemuck::cast_slice_mut(&mut values).copy_from_slice(&bytes);
+        Ok(PodCow::new(Cow::Owned(values)))
+    }
+}
+
+fn cast_error<T, E>(len: usize, err: PodCastError) -> E
+where
+    E: Error,
+{
+    match (mem::size_of::<T>(), err) {
+        (0, _) => E::custom("cannot deserialize a slice of a zero-sized type"),
+        (size, PodCastError::OutputSliceWouldHaveSlop) => E::invalid_length(len, &Multiple(size)),
+        (_, err) => E::custom(format_args!("cannot cast bytes: {err}")),
+    }
+}
+
+struct Multiple(usize);
+
+impl Expected for Multiple {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a byte string whose length is a multiple of {}",
+            self.0
+        )
+    }
+}
@@ -0,0 +1,306 @@
+use crate::de::Deserialize as BytesDeserialize;
+use crate::ser::Serialize as BytesSerialize;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::{self, Impossible, Serializer};
+
+/// How a byte string is represented in the data format.
+///
+/// The default representation is the one used by `#[serde(with =
+/// "serde_bytes")]`, a native byte string. Other representations trade
+/// compactness for compatibility with formats that lack byte strings.
+pub trait Representation {
+    /// Write `bytes` to the serializer.
+    fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+
+    /// Read bytes from the deserializer and pass them to the visitor.
+    ///
+    /// The visitor is one that expects a byte string, so it accepts at least
+    /// `visit_bytes`.
+    fn deserialize<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Visitor<'de>;
+}
+
+pub(crate) fn serialize<R, T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    R: Representation,
+    T: ?Sized + BytesSerialize,
+    S: Serializer,
+{
+    value.serialize(ReprSerializer::<R, S> {
+        serializer,
+        repr: PhantomData,
+    })
+}
+
+pub(crate) fn deserialize<'de, R, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    R: Representation,
+    T: BytesDeserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(ReprDeserializer::<R, D> {
+        deserializer,
+        repr: PhantomData,
+    })
+}
+
+// Passes every byte string serialized by a serde_bytes::Serialize impl
+// through the representation R. The only other shape such an impl produces
+// is an Option.
+struct ReprSerializer<R, S> {
+    serializer: S,
+    repr: PhantomData<R>,
+}
+
+struct Reenter<'a, R, T: ?Sized> {
+    value: &'a T,
+    repr: PhantomData<R>,
+}
+
+impl<'a, R, T> ser::Serialize for Reenter<'a, R, T>
+where
+    R: Representation,
+    T: ?Sized + ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.value.serialize(ReprSerializer::<R, S> {
+            serializer,
+            repr: PhantomData,
+        })
+    }
+}
+
+macro_rules! unsupported {
+    ($($method:ident($($arg:ty),*))*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<S::Ok, S::Error> {
+                Err(ser::Error::custom("serde_bytes can only serialize bytes"))
+            }
+        )*
+    };
+}
+
+impl<R, S> Serializer for ReprSerializer<R, S>
+where
+    R: Representation,
+    S: Serializer,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = Impossible<S::Ok, S::Error>;
+    type SerializeTuple = Impossible<S::Ok, S::Error>;
+    type SerializeTupleStruct = Impossible<S::Ok, S::Error>;
+    type SerializeTupleVariant = Impossible<S::Ok, S::Error>;
+    type SerializeMap = Impossible<S::Ok, S::Error>;
+    type SerializeStruct = Impossible<S::Ok, S::Error>;
+    type SerializeStructVariant = Impossible<S::Ok, S::Error>;
+
+    fn is_human_readable(&self) -> bool {
+        self.serializer.is_human_readable()
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<S::Ok, S::Error> {
+        R::serialize(v, self.serializer)
+    }
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        self.serializer.serialize_none()
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.serializer.serialize_some(&Reenter::<R, T> {
+            value,
+            repr: PhantomData,
+        })
+    }
+
+    unsupported! {
+        serialize_bool(bool)
+        serialize_i8(i8)
+        serialize_i16(i16)
+        serialize_i32(i32)
+        serialize_i64(i64)
+        serialize_u8(u8)
+        serialize_u16(u16)
+        serialize_u32(u32)
+        serialize_u64(u64)
+        serialize_f32(f32)
+        serialize_f64(f64)
+        serialize_char(char)
+        serialize_str(&str)
+        serialize_unit()
+        serialize_unit_struct(&'static str)
+        serialize_unit_variant(&'static str, u32, &'static str)
+    }
+
+    fn collect_str<T>(self, _value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + fmt::Display,
+    {
+        Err(ser::Error::custom("serde_bytes can only serialize bytes"))
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, _value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        Err(ser::Error::custom("serde_bytes can only serialize bytes"))
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        Err(ser::Error::custom("serde_bytes can only serialize bytes"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        Err(ser::Error::custom("serde_bytes can only serialize bytes"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        Err(ser::Error::custom("serde_bytes can only serialize bytes"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        Err(ser::Error::custom("serde_bytes can only serialize bytes"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        Err(ser::Error::custom("serde_bytes can only serialize bytes"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        Err(ser::Error::custom("serde_bytes can only serialize bytes"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        Err(ser::Error::custom("serde_bytes can only serialize bytes"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        Err(ser::Error::custom("serde_bytes can only serialize bytes"))
+    }
+}
+
+// Reads every byte string requested by a serde_bytes::Deserialize impl
+// through the representation R. Options are forwarded to the underlying
+// deserializer so that the representation only sees the content.
+struct ReprDeserializer<R, D> {
+    deserializer: D,
+    repr: PhantomData<R>,
+}
+
+impl<'de, R, D> Deserializer<'de> for ReprDeserializer<R, D>
+where
+    R: Representation,
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    fn is_human_readable(&self) -> bool {
+        self.deserializer.is_human_readable()
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        R::deserialize(self.deserializer, visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserializer.deserialize_option(OptionVisitor::<R, V> {
+            visitor,
+            repr: PhantomData,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple tuple_struct
+        map struct enum identifier ignored_any
+    }
+}
+
+struct OptionVisitor<R, V> {
+    visitor: V,
+    repr: PhantomData<R>,
+}
+
+impl<'de, R, V> Visitor<'de> for OptionVisitor<R, V>
+where
+    R: Representation,
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    fn visit_none<E>(self) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visitor.visit_none()
+    }
+
+    fn visit_unit<E>(self) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visitor.visit_none()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.visitor.visit_some(ReprDeserializer::<R, D> {
+            deserializer,
+            repr: PhantomData,
+        })
+    }
+}
@@ -1,8 +1,9 @@
-use crate::{ByteArray, Bytes};
+use crate::aligned::{Align, Alignment};
+use crate::{AlignedByteArray, ByteArray, Bytes, MaybeBorrowed};
 use serde::Serializer;
 
 #[cfg(any(feature = "std", feature = "alloc"))]
-use crate::ByteBuf;
+use crate::{AlignedByteBuf, ByteBuf};
 
 #[cfg(feature = "alloc")]
 use alloc::borrow::Cow;
@@ -12,6 +13,13 @@ use std::borrow::Cow;
 #[cfg(feature = "alloc")]
 use alloc::boxed::Box;
 
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+#[cfg(all(feature = "std", not(feature = "alloc")))]
+use std::collections::VecDeque;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
@@ -69,6 +77,15 @@ impl<const N: usize> Serialize for ByteArray<N> {
     }
 }
 
+impl<'a, const N: usize> Serialize for MaybeBorrowed<'a, N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&**self)
+    }
+}
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 impl Serialize for ByteBuf {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -79,6 +96,31 @@ impl Serialize for ByteBuf {
     }
 }
 
+impl<const N: usize, const ALIGN: usize> Serialize for AlignedByteArray<N, ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&**self)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<const ALIGN: usize> Serialize for AlignedByteBuf<ALIGN>
+where
+    Align<ALIGN>: Alignment,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self)
+    }
+}
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 impl<'a> Serialize for Cow<'a, [u8]> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -99,6 +141,60 @@ impl<'a> Serialize for Cow<'a, Bytes> {
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, const N: usize> Serialize for Cow<'a, [u8; N]> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&**self)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, const N: usize> Serialize for Cow<'a, ByteArray<N>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&***self)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Serialize for VecDeque<u8> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Only a ring buffer that wraps around needs to be copied to make
+        // its contents contiguous.
+        match self.as_slices() {
+            (front, []) => serializer.serialize_bytes(front),
+            (front, back) => serializer.serialize_bytes(&[front, back].concat()),
+        }
+    }
+}
+
+impl Serialize for str {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self.as_bytes())
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Serialize for String {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self.as_bytes())
+    }
+}
+
 impl<T> Serialize for &T
 where
     T: ?Sized + Serialize,
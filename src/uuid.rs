@@ -0,0 +1,70 @@
+//! UUIDs as bytes in binary formats, text in human-readable formats.
+//!
+//! Available with the `uuid` feature. A `Uuid` is written as a 16-byte
+//! string in binary formats and in the hyphenated form, such as
+//! `67e55044-10b1-426f-9247-bb680e5fe0c8`, in human-readable formats.
+//! Deserialization accepts any form that `Uuid::parse_str` does.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//! use uuid::Uuid;
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Session {
+//!     #[serde(with = "serde_bytes::uuid")]
+//!     id: Uuid,
+//! }
+//!
+//! let session = Session {
+//!     id: Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8),
+//! };
+//! let json = serde_json::to_string(&session).unwrap();
+//! assert_eq!(json, r#"{"id":"67e55044-10b1-426f-9247-bb680e5fe0c8"}"#);
+//! ```
+
+use crate::address::{self, Address};
+use crate::ByteArray;
+use ::uuid::Uuid;
+use core::fmt::{self, Display};
+use serde::de::{Deserializer, Error};
+use serde::ser::Serializer;
+
+/// Serde `serialize_with` function to serialize a UUID as bytes or text.
+pub fn serialize<S>(uuid: &Uuid, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    address::serialize(uuid, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize a UUID from bytes or
+/// text.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Uuid, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    address::deserialize(deserializer)
+}
+
+impl Address for Uuid {
+    type Octets = ByteArray<16>;
+
+    const EXPECTING: &'static str = "a UUID";
+
+    fn octets(&self) -> Self::Octets {
+        ByteArray::new(*self.as_bytes())
+    }
+
+    fn from_octets<E: Error>(octets: Self::Octets) -> Result<Self, E> {
+        Ok(Uuid::from_bytes(octets.into_array()))
+    }
+
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(self, formatter)
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        Uuid::parse_str(text).ok()
+    }
+}
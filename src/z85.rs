@@ -0,0 +1,137 @@
+//! Z85 text in human-readable formats, a native byte string otherwise.
+//!
+//! Z85 is the Base85 variant specified by `ZeroMQ`. It encodes each 4 bytes as
+//! 5 characters, so the text is 25% larger than the bytes compared to 33% for
+//! Base64, and the alphabet avoids quotes and backslashes so that it can be
+//! embedded in JSON without escaping.
+//!
+//! Z85 has no padding: serializing a byte string whose length is not a
+//! multiple of 4 to a human-readable format is an error.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Frame {
+//!     #[serde(with = "serde_bytes::z85")]
+//!     payload: Vec<u8>,
+//! }
+//!
+//! let frame = Frame {
+//!     payload: vec![0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b],
+//! };
+//! let json = serde_json::to_string(&frame).unwrap();
+//! assert_eq!(json, r#"{"payload":"HelloWorld"}"#);
+//!
+//! let frame = Frame { payload: vec![0; 3] };
+//! let err = serde_json::to_string(&frame).unwrap_err();
+//! assert_eq!(
+//!     err.to_string(),
+//!     "Z85 requires a length that is a multiple of 4, got 3 bytes",
+//! );
+//! ```
+
+use crate::adaptive::{Adaptive, DecodeError, Encoding, Representation};
+use crate::de::Deserialize;
+use crate::ser::Serialize;
+use serde::de::{Deserializer, Visitor};
+use serde::ser::{self, Serializer};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Serde `serialize_with` function to serialize bytes as Z85.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Adaptive::<Z85>::serialize(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize bytes from Z85.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Adaptive::<Z85>::deserialize(deserializer)
+}
+
+/// Z85 as in `ZeroMQ` RFC 32. Serialization fails on bytes whose length is
+/// not a multiple of 4.
+pub enum Z85 {}
+
+impl Representation for Z85 {
+    fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if bytes.len() % 4 != 0 {
+            return Err(ser::Error::custom(format_args!(
+                "Z85 requires a length that is a multiple of 4, got {} bytes",
+                bytes.len(),
+            )));
+        }
+        serializer.serialize_str(&Z85Text::encode(bytes))
+    }
+
+    fn deserialize<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Visitor<'de>,
+    {
+        Z85Text::deserialize(deserializer, visitor)
+    }
+}
+
+// The string side of Z85, kept separate because the encoding itself cannot
+// report the length error.
+enum Z85Text {}
+
+impl Encoding for Z85Text {
+    const NAME: &'static str = "z85";
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn encode(bytes: &[u8]) -> String {
+        let mut text = String::with_capacity(bytes.len() / 4 * 5);
+        for chunk in bytes.chunks_exact(4) {
+            let mut value = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            let mut digits = [0; 5];
+            for digit in digits.iter_mut().rev() {
+                *digit = ALPHABET[(value % 85) as usize];
+                value /= 85;
+            }
+            text.extend(digits.iter().map(|&c| char::from(c)));
+        }
+        text
+    }
+
+    fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        if text.len() % 5 != 0 {
+            return Err(DecodeError::invalid_length(text.len(), "a multiple of 5"));
+        }
+
+        let mut bytes = Vec::with_capacity(text.len() / 5 * 4);
+        for (start, chunk) in (0..).step_by(5).zip(text.as_bytes().chunks(5)) {
+            let mut value = 0u32;
+            for (i, &c) in chunk.iter().enumerate() {
+                // A digit is also invalid if it makes the group exceed 32
+                // bits.
+                let digit = (0..).zip(ALPHABET).find(|&(_, &a)| a == c);
+                match digit.and_then(|(d, _)| value.checked_mul(85)?.checked_add(d)) {
+                    Some(next) => value = next,
+                    None => return Err(DecodeError::invalid_character(text, start + i)),
+                }
+            }
+            bytes.extend_from_slice(&value.to_be_bytes());
+        }
+        Ok(bytes)
+    }
+}
+
+const ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
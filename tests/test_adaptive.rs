@@ -0,0 +1,238 @@
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde_bytes::adaptive::{
+    Adaptive, Array, Base64, Base64Url, DecodeError, DecodeErrorKind, Encoding, Hex,
+    OptionallyPrefixed, Prefixed, Utf8,
+};
+use serde_bytes::{ByteArray, ByteBuf};
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{
+    assert_de_tokens, assert_de_tokens_error, assert_tokens, Compact, Configure, Readable, Token,
+};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Test {
+    #[serde(with = "Adaptive::<Hex>")]
+    hex: ByteArray<2>,
+    #[serde(with = "Adaptive::<Base64>")]
+    base64: Vec<u8>,
+    #[serde(with = "Adaptive::<Base64Url>")]
+    base64url: ByteBuf,
+    #[serde(with = "Adaptive::<Array>")]
+    array: [u8; 2],
+    #[serde(with = "Adaptive::<Utf8>")]
+    utf8: Option<Vec<u8>>,
+}
+
+#[test]
+fn test_json() {
+    let test = Test {
+        hex: ByteArray::new([0xab, 0x01]),
+        base64: vec![0xfb, 0xff],
+        base64url: ByteBuf::from(vec![0xfb, 0xff]),
+        array: [1, 2],
+        utf8: Some(b"text".to_vec()),
+    };
+    let json = serde_json::to_string(&test).unwrap();
+    assert_eq!(
+        json,
+        r#"{"hex":"ab01","base64":"+/8=","base64url":"-_8","array":[1,2],"utf8":"text"}"#,
+    );
+    assert_eq!(serde_json::from_str::<Test>(&json).unwrap(), test);
+
+    let upper = r#"{"hex":"AB01","base64":"+/8=","base64url":"-_8","array":[1,2],"utf8":null}"#;
+    let test = Test { utf8: None, ..test };
+    assert_eq!(serde_json::from_str::<Test>(upper).unwrap(), test);
+}
+
+#[test]
+fn test_compact() {
+    let test = Test {
+        hex: ByteArray::new([0xab, 0x01]),
+        base64: vec![1],
+        base64url: ByteBuf::from(vec![2]),
+        array: [3, 4],
+        utf8: None,
+    };
+    assert_tokens(
+        &test.clone().compact(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 5,
+            },
+            Token::Str("hex"),
+            Token::Bytes(&[0xab, 0x01]),
+            Token::Str("base64"),
+            Token::Bytes(&[1]),
+            Token::Str("base64url"),
+            Token::Bytes(&[2]),
+            Token::Str("array"),
+            Token::Bytes(&[3, 4]),
+            Token::Str("utf8"),
+            Token::None,
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_errors() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Key(#[serde(with = "Adaptive::<Hex>")] [u8; 2]);
+
+    #[derive(Serialize, PartialEq, Debug)]
+    struct Text(#[serde(with = "Adaptive::<Utf8>")] Vec<u8>);
+
+    assert_de_tokens_error::<Readable<Key>>(
+        &[Token::NewtypeStruct { name: "Key" }, Token::Str("abc")],
+        "invalid length 3, expected a hex string whose length is a multiple of 2",
+    );
+    assert_de_tokens_error::<Readable<Key>>(
+        &[Token::NewtypeStruct { name: "Key" }, Token::Str("abcdef")],
+        "invalid length 3, expected a byte array of length 2",
+    );
+    assert_de_tokens_error::<Compact<Key>>(
+        &[Token::NewtypeStruct { name: "Key" }, Token::Str("abcd")],
+        "invalid length 4, expected a byte array of length 2",
+    );
+
+    let err = serde_json::to_string(&Text(vec![0xff])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "bytes are not valid UTF-8: invalid utf-8 sequence of 1 bytes from index 0",
+    );
+}
+
+#[test]
+fn test_base64() {
+    for (bytes, standard, url) in [
+        (&b""[..], "", ""),
+        (b"f", "Zg==", "Zg"),
+        (b"fo", "Zm8=", "Zm8"),
+        (b"foo", "Zm9v", "Zm9v"),
+        (b"foob", "Zm9vYg==", "Zm9vYg"),
+        (b"fooba", "Zm9vYmE=", "Zm9vYmE"),
+        (b"foobar", "Zm9vYmFy", "Zm9vYmFy"),
+    ] {
+        assert_eq!(Base64::encode(bytes), standard);
+        assert_eq!(Base64::decode(standard).unwrap(), bytes);
+        assert_eq!(Base64Url::encode(bytes), url);
+        assert_eq!(Base64Url::decode(url).unwrap(), bytes);
+    }
+
+    let err = |text| Base64::decode(text).unwrap_err();
+    assert_eq!(err("Zg"), DecodeError::invalid_length(2, "a multiple of 4"));
+    assert_eq!(err("Zh=="), DecodeError::non_canonical(1));
+    assert_eq!(err("Z==="), DecodeError::invalid_character("Z===", 1));
+
+    let err = |text| Base64Url::decode(text).unwrap_err();
+    assert_eq!(err("Zg=="), DecodeError::invalid_character("Zg==", 2));
+    assert_eq!(
+        err("Z"),
+        DecodeError::invalid_length(1, "not 1 more than a multiple of 4"),
+    );
+}
+
+#[test]
+fn test_prefixed() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+    struct Required(#[serde(with = "Adaptive::<Prefixed<Hex>>")] ByteArray<2>);
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+    struct Optional(#[serde(with = "Adaptive::<OptionallyPrefixed<Base64>>")] Vec<u8>);
+
+    let required = Required(ByteArray::new([0xca, 0xfe]));
+    assert_tokens(
+        &required.clone().readable(),
+        &[
+            Token::NewtypeStruct { name: "Required" },
+            Token::Str("0xcafe"),
+        ],
+    );
+    assert_tokens(
+        &required.compact(),
+        &[
+            Token::NewtypeStruct { name: "Required" },
+            Token::Bytes(&[0xca, 0xfe]),
+        ],
+    );
+
+    let optional = Optional(vec![0xca, 0xfe]);
+    assert_tokens(
+        &optional.clone().readable(),
+        &[
+            Token::NewtypeStruct { name: "Optional" },
+            Token::Str("base64:yv4="),
+        ],
+    );
+    assert_de_tokens(
+        &optional.readable(),
+        &[
+            Token::NewtypeStruct { name: "Optional" },
+            Token::Str("yv4="),
+        ],
+    );
+
+    assert_de_tokens_error::<Readable<Required>>(
+        &[
+            Token::NewtypeStruct { name: "Required" },
+            Token::Str("cafe"),
+        ],
+        "invalid value: string \"cafe\", expected a hex string starting with `0x`",
+    );
+    assert_de_tokens_error::<Readable<Required>>(
+        &[
+            Token::NewtypeStruct { name: "Required" },
+            Token::Str("0xcafg"),
+        ],
+        "invalid value: character `g`, expected a valid hex character at offset 5",
+    );
+    assert_de_tokens_error::<Readable<Required>>(
+        &[
+            Token::NewtypeStruct { name: "Required" },
+            Token::Str("0xcafe00"),
+        ],
+        "invalid length 3, expected a byte array of length 2",
+    );
+    assert_de_tokens_error::<Readable<Optional>>(
+        &[Token::NewtypeStruct { name: "Optional" }, Token::Str("yv4")],
+        "invalid length 3, expected a base64 string whose length is a multiple of 4",
+    );
+    assert_de_tokens_error::<Readable<Optional>>(
+        &[
+            Token::NewtypeStruct { name: "Optional" },
+            Token::Str("base64:yv4"),
+        ],
+        "invalid length 3, expected a base64 string whose length is a multiple of 4",
+    );
+}
+
+#[test]
+fn test_decode_error() {
+    let err = Hex::decode("abé").unwrap_err();
+    assert_eq!(err.kind(), DecodeErrorKind::InvalidCharacter);
+    assert_eq!(err.offset(), 2);
+    assert_eq!(err.character(), Some('é'));
+    assert_eq!(err.to_string(), "invalid character 'é' at offset 2");
+
+    let err = Base64::decode("Zg=").unwrap_err();
+    assert_eq!(err.kind(), DecodeErrorKind::InvalidLength);
+    assert_eq!(err.offset(), 3);
+    assert_eq!(err.expected_length(), Some("a multiple of 4"));
+    assert_eq!(
+        err.to_string(),
+        "invalid length 3, expected a multiple of 4"
+    );
+
+    let err = Base64::decode("Zh==").unwrap_err();
+    assert_eq!(err.kind(), DecodeErrorKind::NonCanonical);
+    assert_eq!(err.to_string(), "non-canonical encoding at offset 1");
+
+    let json = r#"{"hex":"ab0g","base64":"","base64url":"","array":[1,2],"utf8":null}"#;
+    let err = serde_json::from_str::<Test>(json).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: character `g`, expected a valid hex character at offset 3 at line 1 column 13",
+    );
+}
@@ -0,0 +1,147 @@
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{
+    assert_de_tokens, assert_de_tokens_error, assert_tokens, Compact, Configure, Readable, Token,
+};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Test {
+    #[serde(with = "serde_bytes::ip")]
+    v4: Ipv4Addr,
+    #[serde(with = "serde_bytes::ip")]
+    v6: Ipv6Addr,
+    #[serde(with = "serde_bytes::ip")]
+    ip: IpAddr,
+    #[serde(with = "serde_bytes::mac")]
+    mac: [u8; 6],
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Ip(#[serde(with = "serde_bytes::ip")] IpAddr);
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Mac(#[serde(with = "serde_bytes::mac")] [u8; 6]);
+
+#[test]
+fn test_tokens() {
+    let test = Test {
+        v4: Ipv4Addr::new(192, 0, 2, 1),
+        v6: Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+        ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
+        mac: [0x00, 0x1b, 0x44, 0x11, 0x3a, 0xb7],
+    };
+    assert_tokens(
+        &test.clone().readable(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 4,
+            },
+            Token::Str("v4"),
+            Token::Str("192.0.2.1"),
+            Token::Str("v6"),
+            Token::Str("2001:db8::1"),
+            Token::Str("ip"),
+            Token::Str("127.0.0.1"),
+            Token::Str("mac"),
+            Token::Str("00:1b:44:11:3a:b7"),
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &test.compact(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 4,
+            },
+            Token::Str("v4"),
+            Token::Bytes(&[192, 0, 2, 1]),
+            Token::Str("v6"),
+            Token::Bytes(&[0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+            Token::Str("ip"),
+            Token::Bytes(&[127, 0, 0, 1]),
+            Token::Str("mac"),
+            Token::Bytes(&[0x00, 0x1b, 0x44, 0x11, 0x3a, 0xb7]),
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &Ip(IpAddr::V6(Ipv6Addr::LOCALHOST)).compact(),
+        &[
+            Token::NewtypeStruct { name: "Ip" },
+            Token::Bytes(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+        ],
+    );
+}
+
+#[test]
+fn test_errors() {
+    assert_de_tokens_error::<Compact<Ip>>(
+        &[Token::NewtypeStruct { name: "Ip" }, Token::Bytes(&[0; 5])],
+        "invalid length 5, expected 4 or 16 bytes",
+    );
+    assert_de_tokens_error::<Readable<Ip>>(
+        &[Token::NewtypeStruct { name: "Ip" }, Token::Str("256.0.0.1")],
+        "invalid value: string \"256.0.0.1\", expected an IP address",
+    );
+    assert_de_tokens_error::<Compact<Mac>>(
+        &[Token::NewtypeStruct { name: "Mac" }, Token::Bytes(&[0; 4])],
+        "invalid length 4, expected a byte array of length 6",
+    );
+    assert_de_tokens_error::<Readable<Mac>>(
+        &[
+            Token::NewtypeStruct { name: "Mac" },
+            Token::Str("00-1b-44-11-3a-b7"),
+        ],
+        "invalid value: string \"00-1b-44-11-3a-b7\", expected a MAC address",
+    );
+    assert_tokens(
+        &Mac([0xab; 6]).readable(),
+        &[
+            Token::NewtypeStruct { name: "Mac" },
+            Token::Str("ab:ab:ab:ab:ab:ab"),
+        ],
+    );
+    assert_de_tokens(
+        &Mac([0xab; 6]).readable(),
+        &[
+            Token::NewtypeStruct { name: "Mac" },
+            Token::Str("AB:ab:Ab:aB:AB:ab"),
+        ],
+    );
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_uuid() {
+    use uuid::Uuid;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Id(#[serde(with = "serde_bytes::uuid")] Uuid);
+
+    let id = Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+    assert_tokens(
+        &Id(id).readable(),
+        &[
+            Token::NewtypeStruct { name: "Id" },
+            Token::Str("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+        ],
+    );
+    assert_tokens(
+        &Id(id).compact(),
+        &[
+            Token::NewtypeStruct { name: "Id" },
+            Token::Bytes(&[
+                0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f,
+                0xe0, 0xc8,
+            ]),
+        ],
+    );
+    assert_de_tokens_error::<Readable<Id>>(
+        &[Token::NewtypeStruct { name: "Id" }, Token::Str("67e55044")],
+        "invalid value: string \"67e55044\", expected a UUID",
+    );
+}
@@ -0,0 +1,95 @@
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde_bytes::{AlignedByteArray, AlignedByteBuf};
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Test {
+    buf: AlignedByteBuf<64>,
+    array: AlignedByteArray<4, 16>,
+    #[serde(with = "serde_bytes")]
+    with: AlignedByteBuf<4096>,
+}
+
+#[test]
+fn test_tokens() {
+    let test = Test {
+        buf: AlignedByteBuf::from(&b"..."[..]),
+        array: AlignedByteArray::new(*b"abcd"),
+        with: AlignedByteBuf::new(),
+    };
+    assert_tokens(
+        &test,
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 3,
+            },
+            Token::Str("buf"),
+            Token::Bytes(b"..."),
+            Token::Str("array"),
+            Token::Bytes(b"abcd"),
+            Token::Str("with"),
+            Token::Bytes(b""),
+            Token::StructEnd,
+        ],
+    );
+    assert_de_tokens(
+        &AlignedByteBuf::<8>::from(&[1, 2, 3][..]),
+        &[
+            Token::Seq { len: Some(3) },
+            Token::U8(1),
+            Token::U8(2),
+            Token::U8(3),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_alignment() {
+    let bytes: Vec<u8> = (0..=255).collect();
+    let mut buf = AlignedByteBuf::<256>::new();
+    for (i, &byte) in bytes.iter().enumerate() {
+        buf.push(byte);
+        assert_eq!(buf.as_ptr() as usize % 256, 0);
+        assert_eq!(*buf, bytes[..=i]);
+    }
+    buf.extend_from_slice(&bytes);
+    assert_eq!(buf.len(), 512);
+    assert_eq!(buf.as_ptr() as usize % 256, 0);
+
+    buf.truncate(3);
+    buf.extend_from_slice(&[9]);
+    assert_eq!(*buf, [0, 1, 2, 9]);
+    buf.clear();
+    assert!(buf.is_empty());
+
+    let json = serde_json::to_string(&bytes).unwrap();
+    let buf: AlignedByteBuf<4096> = serde_json::from_str(&json).unwrap();
+    assert_eq!(buf.as_ptr() as usize % 4096, 0);
+    assert_eq!(buf.to_vec(), bytes);
+
+    let array: Box<AlignedByteArray<3, 64>> = Box::new(AlignedByteArray::new([1, 2, 3]));
+    assert_eq!(array.as_ptr() as usize % 64, 0);
+    assert_eq!(std::mem::align_of::<AlignedByteArray<3, 64>>(), 64);
+}
+
+#[test]
+fn test_errors() {
+    assert_de_tokens_error::<AlignedByteArray<4, 16>>(
+        &[Token::Bytes(b"abc")],
+        "invalid length 3, expected a byte array of length 4",
+    );
+    assert_de_tokens_error::<AlignedByteArray<4, 16>>(
+        &[Token::Seq { len: Some(1) }, Token::U8(1), Token::SeqEnd],
+        "invalid length 1, expected a byte array of length 4",
+    );
+}
+
+#[test]
+#[should_panic = "capacity overflow"]
+fn test_capacity_overflow() {
+    AlignedByteBuf::<64>::with_capacity(usize::MAX);
+}
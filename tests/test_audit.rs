@@ -0,0 +1,80 @@
+use serde_bytes::ByteBuf;
+use serde_derive::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Header {
+    #[serde(with = "serde_bytes")]
+    key_id: Vec<u8>,
+    nonce: [u8; 4],
+}
+
+#[derive(Serialize)]
+enum Body {
+    Chunks(Vec<Vec<u8>>),
+    Tagged { tag: u8, data: ByteBuf },
+}
+
+#[derive(Serialize)]
+struct Message {
+    header: Header,
+    body: Body,
+    extra: BTreeMap<String, Vec<u8>>,
+    empty: Vec<u8>,
+    numbers: Vec<u16>,
+    rgb: Rgb,
+}
+
+#[derive(Serialize)]
+struct Rgb(u8, u8, u8);
+
+#[test]
+fn test_unoptimized() {
+    let mut extra = BTreeMap::new();
+    extra.insert("signature".to_owned(), vec![1, 2]);
+    let message = Message {
+        header: Header {
+            key_id: vec![1],
+            nonce: [0; 4],
+        },
+        body: Body::Chunks(vec![vec![1], vec![2, 3]]),
+        extra,
+        empty: Vec::new(),
+        numbers: vec![1],
+        rgb: Rgb(1, 2, 3),
+    };
+
+    let err = serde_bytes::audit::check(&message).unwrap_err();
+    assert_eq!(
+        err.paths(),
+        [
+            "header.nonce",
+            "body.Chunks[0]",
+            "body.Chunks[1]",
+            "extra[signature]",
+        ],
+    );
+    assert_eq!(
+        err.to_string(),
+        "sequence of u8 serialized at `header.nonce`, `body.Chunks[0]`, `body.Chunks[1]`, `extra[signature]`; use #[serde(with = \"serde_bytes\")]",
+    );
+}
+
+#[test]
+fn test_optimized() {
+    let body = Body::Tagged {
+        tag: 1,
+        data: ByteBuf::from(vec![1, 2, 3]),
+    };
+    serde_bytes::audit::check(&body).unwrap();
+}
+
+#[test]
+fn test_root() {
+    let err = serde_bytes::audit::check(&vec![0u8]).unwrap_err();
+    assert_eq!(err.paths(), [""]);
+    assert_eq!(
+        err.to_string(),
+        "sequence of u8 serialized at the root; use #[serde(with = \"serde_bytes\")]",
+    );
+}
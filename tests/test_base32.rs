@@ -0,0 +1,143 @@
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde_bytes::adaptive::{DecodeError, Encoding};
+use serde_bytes::base32::{Base32, Base32Hex, Base32Unpadded, Crockford};
+use serde_bytes::{ByteArray, ByteBuf};
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Readable, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Test {
+    #[serde(with = "serde_bytes::base32")]
+    padded: Vec<u8>,
+    #[serde(with = "serde_bytes::base32::unpadded")]
+    unpadded: ByteBuf,
+    #[serde(with = "serde_bytes::base32::hex")]
+    hex: ByteArray<3>,
+    #[serde(with = "serde_bytes::base32::crockford")]
+    crockford: Option<[u8; 2]>,
+}
+
+#[test]
+fn test_rfc4648() {
+    for (bytes, padded, hex) in [
+        (&b""[..], "", ""),
+        (b"f", "MY======", "CO======"),
+        (b"fo", "MZXQ====", "CPNG===="),
+        (b"foo", "MZXW6===", "CPNMU==="),
+        (b"foob", "MZXW6YQ=", "CPNMUOG="),
+        (b"fooba", "MZXW6YTB", "CPNMUOJ1"),
+        (b"foobar", "MZXW6YTBOI======", "CPNMUOJ1E8======"),
+    ] {
+        let unpadded = padded.trim_end_matches('=');
+        assert_eq!(Base32::encode(bytes), padded);
+        assert_eq!(Base32Unpadded::encode(bytes), unpadded);
+        assert_eq!(Base32Hex::encode(bytes), hex);
+        assert_eq!(Base32::decode(padded).unwrap(), bytes);
+        assert_eq!(Base32::decode(&padded.to_lowercase()).unwrap(), bytes);
+        assert_eq!(Base32Unpadded::decode(unpadded).unwrap(), bytes);
+        assert_eq!(Base32Hex::decode(&hex.to_lowercase()).unwrap(), bytes);
+    }
+
+    assert_eq!(
+        Base32::decode("MZXW6").unwrap_err(),
+        DecodeError::invalid_length(5, "a multiple of 8"),
+    );
+    assert_eq!(
+        Base32::decode("MZX=====").unwrap_err(),
+        DecodeError::invalid_character("MZX=====", 3),
+    );
+    assert_eq!(
+        Base32::decode("MZ======").unwrap_err(),
+        DecodeError::non_canonical(1),
+    );
+    assert_eq!(
+        Base32Unpadded::decode("MY======").unwrap_err(),
+        DecodeError::invalid_character("MY======", 2),
+    );
+    assert_eq!(
+        Base32Hex::decode("W0======").unwrap_err(),
+        DecodeError::invalid_character("W0======", 0),
+    );
+}
+
+#[test]
+fn test_crockford() {
+    assert_eq!(Crockford::encode(b"foobar"), "CSQPYRK1E8");
+    assert_eq!(Crockford::decode("CSQPYRK1E8").unwrap(), b"foobar");
+    assert_eq!(Crockford::decode("csqp-yrki-e8").unwrap(), b"foobar");
+    assert_eq!(Crockford::decode("0o").unwrap(), b"\0");
+    assert_eq!(
+        Crockford::decode("CS-UA").unwrap_err(),
+        DecodeError::invalid_character("CS-UA", 3),
+    );
+    assert_eq!(
+        Crockford::decode("ABCD-EF").unwrap_err(),
+        DecodeError::invalid_length(
+            6,
+            "a number of digits not 1, 3 or 6 more than a multiple of 8",
+        ),
+    );
+}
+
+#[test]
+fn test_tokens() {
+    let test = Test {
+        padded: b"f".to_vec(),
+        unpadded: ByteBuf::from(b"fo".to_vec()),
+        hex: ByteArray::new(*b"foo"),
+        crockford: Some(*b"fo"),
+    };
+    assert_tokens(
+        &test.clone().readable(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 4,
+            },
+            Token::Str("padded"),
+            Token::Str("MY======"),
+            Token::Str("unpadded"),
+            Token::Str("MZXQ"),
+            Token::Str("hex"),
+            Token::Str("CPNMU==="),
+            Token::Str("crockford"),
+            Token::Some,
+            Token::Str("CSQG"),
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &test.compact(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 4,
+            },
+            Token::Str("padded"),
+            Token::Bytes(b"f"),
+            Token::Str("unpadded"),
+            Token::Bytes(b"fo"),
+            Token::Str("hex"),
+            Token::Bytes(b"foo"),
+            Token::Str("crockford"),
+            Token::Some,
+            Token::Bytes(b"fo"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_errors() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Secret(#[serde(with = "serde_bytes::base32::unpadded")] ByteBuf);
+
+    assert_de_tokens_error::<Readable<Secret>>(
+        &[
+            Token::NewtypeStruct { name: "Secret" },
+            Token::Str("MY======"),
+        ],
+        "invalid value: character `=`, expected a valid base32nopad character at offset 2",
+    );
+}
@@ -0,0 +1,111 @@
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde_bytes::adaptive::{DecodeError, Encoding};
+use serde_bytes::base58::Base58;
+use serde_bytes::{ByteArray, ByteBuf};
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Readable, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Test {
+    #[serde(with = "serde_bytes::base58")]
+    hash: ByteArray<4>,
+    #[serde(with = "serde_bytes::base58check")]
+    address: ByteBuf,
+    #[serde(with = "serde_bytes::base58check")]
+    array: [u8; 1],
+}
+
+#[test]
+fn test_base58() {
+    for (bytes, text) in [
+        (&b""[..], ""),
+        (b"\0", "1"),
+        (b"\0\0\x01", "112"),
+        (b"\x61", "2g"),
+        (b"\x62\x62\x62", "a3gV"),
+        (b"Hello World!", "2NEpo7TZRRrLZSi2U"),
+        (
+            b"\x00\xeb\x15\x23\x1d\xfc\xeb\x60\x92\x58\x86\xb6\x7d\x06\x52\x99\x92\x59\x15\xae\xb1\x72\xc0\x66\x47",
+            "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L",
+        ),
+    ] {
+        assert_eq!(Base58::encode(bytes), text);
+        assert_eq!(Base58::decode(text).unwrap(), bytes);
+    }
+
+    assert_eq!(
+        Base58::decode("10").unwrap_err(),
+        DecodeError::invalid_character("10", 1),
+    );
+    assert_eq!(
+        Base58::decode("Il").unwrap_err(),
+        DecodeError::invalid_character("Il", 0),
+    );
+}
+
+#[test]
+fn test_tokens() {
+    let test = Test {
+        hash: ByteArray::new([0, 0, 0, 1]),
+        address: ByteBuf::from(
+            b"\x00\xeb\x15\x23\x1d\xfc\xeb\x60\x92\x58\x86\xb6\x7d\x06\x52\x99\x92\x59\x15\xae\xb1"
+                .to_vec(),
+        ),
+        array: [0],
+    };
+    assert_tokens(
+        &test.clone().readable(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 3,
+            },
+            Token::Str("hash"),
+            Token::Str("1112"),
+            Token::Str("address"),
+            Token::Str("1NS17iag9jJgTHD1VXjvLCEnZuQ3rJED9L"),
+            Token::Str("array"),
+            Token::Str("1Wh4bh"),
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &test.compact(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 3,
+            },
+            Token::Str("hash"),
+            Token::Bytes(&[0, 0, 0, 1]),
+            Token::Str("address"),
+            Token::Bytes(b"\x00\xeb\x15\x23\x1d\xfc\xeb\x60\x92\x58\x86\xb6\x7d\x06\x52\x99\x92\x59\x15\xae\xb1"),
+            Token::Str("array"),
+            Token::Bytes(&[0]),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_errors() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Check(#[serde(with = "serde_bytes::base58check")] ByteBuf);
+
+    assert_de_tokens_error::<Readable<Check>>(
+        &[Token::NewtypeStruct { name: "Check" }, Token::Str("1NS17iag9jJgTHD1VXjvLCEnZuQ3rJED9M")],
+        "invalid value: string \"1NS17iag9jJgTHD1VXjvLCEnZuQ3rJED9M\", expected a base58check string with a valid checksum",
+    );
+    assert_de_tokens_error::<Readable<Check>>(
+        &[
+            Token::NewtypeStruct { name: "Check" },
+            Token::Str("1NS17iag9jJgTHD1VXjvLCEnZuQ3rJED9O"),
+        ],
+        "invalid value: character `O`, expected a valid base58check character at offset 33",
+    );
+    assert_de_tokens_error::<Readable<Check>>(
+        &[Token::NewtypeStruct { name: "Check" }, Token::Str("2g")],
+        "invalid value: string \"2g\", expected a base58check string with a 4-byte checksum",
+    );
+}
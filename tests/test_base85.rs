@@ -0,0 +1,109 @@
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde_bytes::adaptive::{DecodeError, Encoding};
+use serde_bytes::ascii85::Ascii85;
+use serde_bytes::ByteBuf;
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{
+    assert_de_tokens_error, assert_ser_tokens_error, assert_tokens, Configure, Readable, Token,
+};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Test {
+    #[serde(with = "serde_bytes::z85")]
+    z85: [u8; 8],
+    #[serde(with = "serde_bytes::ascii85")]
+    ascii85: ByteBuf,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Z85(#[serde(with = "serde_bytes::z85")] Vec<u8>);
+
+#[test]
+fn test_ascii85() {
+    for (bytes, text) in [
+        (&b""[..], ""),
+        (b"Man ", "9jqo^"),
+        (b"sure.", "F*2M7/c"),
+        (b"\0\0\0\0\x01", "z!<"),
+        (b"\0\0\0", "!!!!"),
+        (b"\xff\xff\xff\xff", "s8W-!"),
+    ] {
+        assert_eq!(Ascii85::encode(bytes), text);
+        assert_eq!(Ascii85::decode(text).unwrap(), bytes);
+    }
+
+    for (text, err) in [
+        ("!!!!!", DecodeError::non_canonical(0)),
+        ("F*2M7/d", DecodeError::non_canonical(6)),
+        (
+            "9jqo^F",
+            DecodeError::invalid_length(
+                6,
+                "not 1 more than a multiple of 5, counting each `z` as 5",
+            ),
+        ),
+        ("s8W-\"", DecodeError::invalid_character("s8W-\"", 4)),
+        ("9jqzo^", DecodeError::invalid_character("9jqzo^", 3)),
+        ("<~9jqo^~>", DecodeError::invalid_character("<~9jqo^~>", 1)),
+    ] {
+        assert_eq!(Ascii85::decode(text).unwrap_err(), err, "{text:?}");
+    }
+}
+
+#[test]
+fn test_tokens() {
+    let test = Test {
+        z85: [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b],
+        ascii85: ByteBuf::from(b"Man sure.".to_vec()),
+    };
+    assert_tokens(
+        &test.clone().readable(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 2,
+            },
+            Token::Str("z85"),
+            Token::Str("HelloWorld"),
+            Token::Str("ascii85"),
+            Token::Str("9jqo^F*2M7/c"),
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &test.compact(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 2,
+            },
+            Token::Str("z85"),
+            Token::Bytes(&[0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b]),
+            Token::Str("ascii85"),
+            Token::Bytes(b"Man sure."),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_z85_errors() {
+    assert_ser_tokens_error(
+        &Z85(vec![0; 5]).readable(),
+        &[Token::NewtypeStruct { name: "Z85" }],
+        "Z85 requires a length that is a multiple of 4, got 5 bytes",
+    );
+    assert_de_tokens_error::<Readable<Z85>>(
+        &[Token::NewtypeStruct { name: "Z85" }, Token::Str("Hell~")],
+        "invalid value: character `~`, expected a valid z85 character at offset 4",
+    );
+    assert_de_tokens_error::<Readable<Z85>>(
+        &[Token::NewtypeStruct { name: "Z85" }, Token::Str("Hell")],
+        "invalid length 4, expected a z85 string whose length is a multiple of 5",
+    );
+    assert_de_tokens_error::<Readable<Z85>>(
+        &[Token::NewtypeStruct { name: "Z85" }, Token::Str("#####")],
+        "invalid value: character `#`, expected a valid z85 character at offset 4",
+    );
+}
@@ -0,0 +1,129 @@
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde_bytes::adaptive::Adaptive;
+use serde_bytes::bech32::{Bech32, Bech32m, Hrp};
+use serde_bytes::{ByteArray, ByteBuf};
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{
+    assert_de_tokens, assert_de_tokens_error, assert_tokens, Configure, Readable, Token,
+};
+
+enum Key {}
+
+impl Hrp for Key {
+    const HRP: &'static str = "key";
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Test {
+    #[serde(with = "Adaptive::<Bech32<Key>>")]
+    bech32: ByteArray<3>,
+    #[serde(with = "Adaptive::<Bech32m<Key>>")]
+    bech32m: ByteBuf,
+    #[serde(with = "Adaptive::<Bech32m<Key>>")]
+    empty: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Id(#[serde(with = "Adaptive::<Bech32m<Key>>")] ByteBuf);
+
+#[test]
+fn test_tokens() {
+    let test = Test {
+        bech32: ByteArray::new([1, 2, 3]),
+        bech32m: ByteBuf::from(vec![1, 2, 3]),
+        empty: Vec::new(),
+    };
+    assert_tokens(
+        &test.clone().readable(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 3,
+            },
+            Token::Str("bech32"),
+            Token::Str("key1qypqx7ck48c"),
+            Token::Str("bech32m"),
+            Token::Str("key1qypqxtyxez6"),
+            Token::Str("empty"),
+            Token::Str("key17melu0"),
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &test.compact(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 3,
+            },
+            Token::Str("bech32"),
+            Token::Bytes(&[1, 2, 3]),
+            Token::Str("bech32m"),
+            Token::Bytes(&[1, 2, 3]),
+            Token::Str("empty"),
+            Token::Bytes(&[]),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_uppercase() {
+    assert_de_tokens(
+        &Id(ByteBuf::from(vec![1, 2, 3])).readable(),
+        &[
+            Token::NewtypeStruct { name: "Id" },
+            Token::Str("KEY1QYPQXTYXEZ6"),
+        ],
+    );
+}
+
+#[test]
+fn test_errors() {
+    for (text, expected) in [
+        (
+            "key1qypqxtyxez7",
+            "invalid value: string \"key1qypqxtyxez7\", expected a bech32m string with a valid checksum",
+        ),
+        // Valid Bech32, but not Bech32m.
+        (
+            "key1qypqx7ck48c",
+            "invalid value: string \"key1qypqx7ck48c\", expected a bech32m string with a valid checksum",
+        ),
+        (
+            "kez1qypqxtyxez6",
+            "invalid value: string \"kez1qypqxtyxez6\", expected a bech32m string with human-readable part `key`",
+        ),
+        (
+            "keyqypqxtyxez6",
+            "invalid value: string \"keyqypqxtyxez6\", expected a bech32m string with human-readable part `key`",
+        ),
+        (
+            "key1qypqx",
+            "invalid value: string \"key1qypqx\", expected a bech32m string with a 6-character checksum",
+        ),
+        (
+            "Key1qypqxtyxez6",
+            "invalid value: character `e`, expected a valid bech32m character at offset 1",
+        ),
+        (
+            "key1qypqxbyxez6",
+            "invalid value: character `b`, expected a valid bech32m character at offset 9",
+        ),
+        (
+            "key1qypqxé",
+            "invalid value: character `é`, expected a valid bech32m character at offset 9",
+        ),
+        // Five 5-bit groups hold 3 bytes and 1 nonzero padding bit.
+        (
+            "key1qypq8kjjvlg",
+            "invalid value: string \"key1qypq8kjjvlg\", expected canonical bech32m at offset 8",
+        ),
+    ] {
+        assert_de_tokens_error::<Readable<Id>>(
+            &[Token::NewtypeStruct { name: "Id" }, Token::Str(text)],
+            expected,
+        );
+    }
+}
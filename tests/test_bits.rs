@@ -0,0 +1,133 @@
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde_bytes::bits::{Lsb0, Msb0};
+use serde_bytes::{BitArray, BitBuf};
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Test {
+    #[serde(with = "serde_bytes::bits")]
+    msb0: Vec<bool>,
+    #[serde(with = "serde_bytes::bits::lsb0")]
+    lsb0: Vec<bool>,
+    buf: BitBuf<Lsb0>,
+    array: BitArray<3, 1>,
+}
+
+#[test]
+fn test_tokens() {
+    let bits = vec![true, false, false, false, false, false, false, true, true];
+    let test = Test {
+        msb0: bits.clone(),
+        lsb0: bits.clone(),
+        buf: BitBuf::from(bits),
+        array: BitArray::from([false, true, true]),
+    };
+    assert_tokens(
+        &test,
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 4,
+            },
+            Token::Str("msb0"),
+            Token::Tuple { len: 2 },
+            Token::U64(9),
+            Token::Bytes(&[0x81, 0x80]),
+            Token::TupleEnd,
+            Token::Str("lsb0"),
+            Token::Tuple { len: 2 },
+            Token::U64(9),
+            Token::Bytes(&[0x81, 0x01]),
+            Token::TupleEnd,
+            Token::Str("buf"),
+            Token::Tuple { len: 2 },
+            Token::U64(9),
+            Token::Bytes(&[0x81, 0x01]),
+            Token::TupleEnd,
+            Token::Str("array"),
+            Token::Tuple { len: 2 },
+            Token::U64(3),
+            Token::Bytes(&[0x60]),
+            Token::TupleEnd,
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &BitBuf::<Msb0>::new(),
+        &[
+            Token::Tuple { len: 2 },
+            Token::U64(0),
+            Token::Bytes(&[]),
+            Token::TupleEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_bit_buf() {
+    let mut bits = BitBuf::<Msb0>::new();
+    for i in 0..10 {
+        bits.push(i % 3 == 0);
+    }
+    bits.set(1, true);
+    assert_eq!(bits.len(), 10);
+    assert_eq!(bits.get(1), Some(true));
+    assert_eq!(bits.get(10), None);
+    assert_eq!(bits.as_bytes(), [0b1101_0010, 0b0100_0000]);
+    assert_eq!(Vec::from(bits).len(), 10);
+}
+
+#[test]
+fn test_bit_array() {
+    let mut bits = BitArray::<10, 2, Lsb0>::from([true; 10]);
+    bits.set(9, false);
+    assert_eq!(bits.len(), 10);
+    assert_eq!(bits.get(9), Some(false));
+    assert_eq!(bits.get(10), None);
+    assert_eq!(bits.as_bytes(), &[0xff, 0x01]);
+    assert_eq!(<[bool; 10]>::from(bits)[8..], [true, false]);
+}
+
+#[test]
+fn test_errors() {
+    assert_de_tokens_error::<BitBuf>(
+        &[Token::Tuple { len: 2 }, Token::U64(9), Token::Bytes(&[0])],
+        "invalid length 1, expected 2 packed bytes for 9 bits",
+    );
+    assert_de_tokens_error::<BitBuf>(
+        &[
+            Token::Tuple { len: 2 },
+            Token::U64(9),
+            Token::Bytes(&[0, 0x40]),
+        ],
+        "invalid value: integer `64`, expected a last byte with zero padding bits",
+    );
+    assert_de_tokens_error::<BitArray<3, 1, Lsb0>>(
+        &[Token::Tuple { len: 2 }, Token::U64(4)],
+        "invalid value: integer `4`, expected a bit length of 3 and packed bytes",
+    );
+}
+
+#[test]
+fn test_overflowing_len() {
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Wrapper {
+        #[serde(with = "serde_bytes::bits")]
+        f: Vec<bool>,
+    }
+
+    let err = serde_json::from_str::<Wrapper>(r#"{"f":[18446744073709551615,[]]}"#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid length 0, expected 2305843009213693952 packed bytes for 18446744073709551615 bits at line 1 column 30",
+    );
+
+    let err = serde_json::from_str::<BitBuf>("[18446744073709551615,[]]").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid length 0, expected 2305843009213693952 packed bytes for 18446744073709551615 bits at line 1 column 25",
+    );
+}
@@ -0,0 +1,40 @@
+use serde_bytes::{ByteArray, ByteBuf};
+
+#[test]
+fn test_conversions() {
+    let array = ByteArray::<3>::try_from(&b"abc"[..]).unwrap();
+    assert_eq!(array.as_bytes(), b"abc");
+    assert_eq!(<[u8; 3]>::from(array), *b"abc");
+    let buf: ByteBuf = array.into();
+    assert_eq!(buf, b"abc");
+    assert_eq!(ByteArray::<3>::try_from(b"abc".to_vec()).unwrap(), array);
+    assert_eq!(
+        ByteArray::<3>::try_from(ByteBuf::from("abc")).unwrap(),
+        array
+    );
+
+    let err = ByteArray::<3>::try_from(b"abcd".to_vec()).unwrap_err();
+    assert_eq!(err.expected(), 3);
+    assert_eq!(err.actual(), 4);
+    assert_eq!(err.to_string(), "expected 3 bytes, found 4");
+}
+
+#[test]
+fn test_from_hex() {
+    const EMPTY: ByteArray<0> = ByteArray::from_hex("");
+    const KEY: ByteArray<4> = ByteArray::from_hex("00fF7a9C");
+    assert_eq!(EMPTY, []);
+    assert_eq!(KEY, [0x00, 0xff, 0x7a, 0x9c]);
+}
+
+#[test]
+#[should_panic = "wrong number of hex digits"]
+fn test_from_hex_length() {
+    ByteArray::<2>::from_hex("abc");
+}
+
+#[test]
+#[should_panic = "invalid hex digit"]
+fn test_from_hex_digit() {
+    ByteArray::<2>::from_hex("abcg");
+}
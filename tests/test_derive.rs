@@ -1,9 +1,10 @@
 #![allow(clippy::derive_partial_eq_without_eq, clippy::ref_option_ref)]
 
-use serde_bytes::{ByteArray, ByteBuf, Bytes};
+use serde_bytes::{ByteArray, ByteBuf, Bytes, MaybeBorrowed};
 use serde_derive::{Deserialize, Serialize};
-use serde_test::{assert_tokens, Token};
+use serde_test::{assert_de_tokens_error, assert_ser_tokens, assert_tokens, Token};
 use std::borrow::Cow;
+use std::collections::VecDeque;
 
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 struct Test<'a> {
@@ -37,12 +38,36 @@ struct Test<'a> {
     #[serde(with = "serde_bytes")]
     cow_bytes: Cow<'a, Bytes>,
 
+    #[serde(with = "serde_bytes")]
+    cow_array: Cow<'a, [u8; 314]>,
+
+    #[serde(with = "serde_bytes")]
+    cow_byte_array: Cow<'a, ByteArray<314>>,
+
     #[serde(with = "serde_bytes")]
     boxed_slice: Box<[u8]>,
 
     #[serde(with = "serde_bytes")]
     boxed_bytes: Box<Bytes>,
 
+    #[serde(with = "serde_bytes")]
+    boxed_array: Box<[u8; 314]>,
+
+    #[serde(with = "serde_bytes")]
+    boxed_byte_array: Box<ByteArray<314>>,
+
+    #[serde(with = "serde_bytes")]
+    vec_deque: VecDeque<u8>,
+
+    #[serde(with = "serde_bytes")]
+    str: &'a str,
+
+    #[serde(with = "serde_bytes")]
+    string: String,
+
+    #[serde(with = "serde_bytes")]
+    boxed_str: Box<str>,
+
     #[serde(with = "serde_bytes")]
     opt_slice: Option<&'a [u8]>,
 
@@ -79,8 +104,16 @@ fn test() {
         byte_buf: ByteBuf::from(b"...".as_ref()),
         cow_slice: Cow::Borrowed(b"..."),
         cow_bytes: Cow::Borrowed(Bytes::new(b"...")),
+        cow_array: Cow::Borrowed(&[1; 314]),
+        cow_byte_array: Cow::Owned(ByteArray::new([0; 314])),
         boxed_slice: b"...".to_vec().into_boxed_slice(),
         boxed_bytes: ByteBuf::from(b"...".as_ref()).into_boxed_bytes(),
+        boxed_array: Box::new([0; 314]),
+        boxed_byte_array: Box::new(ByteArray::new([1; 314])),
+        vec_deque: VecDeque::from(b"...".to_vec()),
+        str: "...",
+        string: "...".to_owned(),
+        boxed_str: "...".into(),
         opt_slice: Some(b"..."),
         opt_vec: Some(b"...".to_vec()),
         opt_array: Some([0; 314]),
@@ -93,7 +126,7 @@ fn test() {
         &[
             Token::Struct {
                 name: "Test",
-                len: 17,
+                len: 25,
             },
             Token::Str("slice"),
             Token::BorrowedBytes(b"..."),
@@ -115,10 +148,26 @@ fn test() {
             Token::BorrowedBytes(b"..."),
             Token::Str("cow_bytes"),
             Token::BorrowedBytes(b"..."),
+            Token::Str("cow_array"),
+            Token::BorrowedBytes(&[1; 314]),
+            Token::Str("cow_byte_array"),
+            Token::BorrowedBytes(&[0; 314]),
             Token::Str("boxed_slice"),
             Token::Bytes(b"..."),
             Token::Str("boxed_bytes"),
             Token::Bytes(b"..."),
+            Token::Str("boxed_array"),
+            Token::Bytes(&[0; 314]),
+            Token::Str("boxed_byte_array"),
+            Token::Bytes(&[1; 314]),
+            Token::Str("vec_deque"),
+            Token::Bytes(b"..."),
+            Token::Str("str"),
+            Token::BorrowedBytes(b"..."),
+            Token::Str("string"),
+            Token::Bytes(b"..."),
+            Token::Str("boxed_str"),
+            Token::Bytes(b"..."),
             Token::Str("opt_slice"),
             Token::Some,
             Token::BorrowedBytes(b"..."),
@@ -138,3 +187,116 @@ fn test() {
         ],
     );
 }
+
+#[derive(Deserialize)]
+struct CowArray<'a> {
+    #[serde(with = "serde_bytes", borrow)]
+    array: Cow<'a, [u8; 3]>,
+}
+
+#[test]
+fn test_cow_array() {
+    let borrowed: CowArray = serde_json::from_str(r#"{"array":"..."}"#).unwrap();
+    assert!(matches!(borrowed.array, Cow::Borrowed(b"...")));
+
+    let owned: CowArray = serde_json::from_str(r#"{"array":[46,46,46]}"#).unwrap();
+    assert!(matches!(owned.array, Cow::Owned(ref a) if a == b"..."));
+
+    let err = serde_json::from_str::<CowArray>(r#"{"array":".."}"#)
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "invalid length 2, expected a byte array of length 3 at line 1 column 13",
+    );
+}
+
+#[derive(Deserialize)]
+struct BorrowedArray<'a> {
+    #[serde(with = "serde_bytes", borrow)]
+    array: &'a [u8; 3],
+}
+
+#[derive(Deserialize)]
+struct MaybeBorrowedArray<'a> {
+    #[serde(borrow)]
+    array: MaybeBorrowed<'a, 3>,
+}
+
+#[test]
+fn test_maybe_borrowed() {
+    let borrowed: BorrowedArray = serde_json::from_str(r#"{"array":"..."}"#).unwrap();
+    assert_eq!(borrowed.array, b"...");
+
+    let err = serde_json::from_str::<BorrowedArray>(r#"{"array":".\u002e."}"#)
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "cannot borrow a byte array of length 3 from this input; use MaybeBorrowed or ByteArray to copy it instead at line 1 column 19",
+    );
+
+    let borrowed: MaybeBorrowedArray = serde_json::from_str(r#"{"array":"..."}"#).unwrap();
+    assert!(matches!(borrowed.array, MaybeBorrowed::Borrowed(b"...")));
+
+    let owned: MaybeBorrowedArray = serde_json::from_str(r#"{"array":".\u002e."}"#).unwrap();
+    assert!(matches!(owned.array, MaybeBorrowed::Owned(ref a) if a == b"..."));
+
+    let owned: MaybeBorrowedArray = serde_json::from_str(r#"{"array":[46,46,46]}"#).unwrap();
+    assert!(matches!(owned.array, MaybeBorrowed::Owned(ref a) if a == b"..."));
+
+    assert_tokens(
+        &MaybeBorrowed::from(b"..."),
+        &[Token::BorrowedBytes(b"...")],
+    );
+}
+
+#[derive(Deserialize)]
+struct Large {
+    #[serde(with = "serde_bytes")]
+    array: Box<[u8; 1 << 16]>,
+}
+
+#[test]
+fn test_boxed_array() {
+    let json = format!(r#"{{"array":"{}"}}"#, ".".repeat(1 << 16));
+    let large = std::thread::Builder::new()
+        .stack_size(16 * 1024)
+        .spawn(move || serde_json::from_str::<Large>(&json).unwrap())
+        .unwrap()
+        .join()
+        .unwrap();
+    assert!(large.array.iter().all(|&b| b == b'.'));
+
+    let err = serde_json::from_str::<Large>(r#"{"array":[0,0]}"#)
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "invalid length 2, expected a byte array of length 65536 at line 1 column 14",
+    );
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Text(#[serde(with = "serde_bytes")] String);
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Ring(#[serde(with = "serde_bytes")] VecDeque<u8>);
+
+#[test]
+fn test_text_and_ring() {
+    assert_de_tokens_error::<Text>(
+        &[Token::NewtypeStruct { name: "Text" }, Token::Bytes(b"\xff")],
+        "invalid value: byte array, expected UTF-8 bytes",
+    );
+
+    let mut ring = VecDeque::with_capacity(4);
+    ring.extend(b"..ab");
+    ring.drain(..2);
+    ring.extend(b"cd");
+    assert!(!ring.as_slices().1.is_empty());
+    assert_ser_tokens(
+        &Ring(ring),
+        &[Token::NewtypeStruct { name: "Ring" }, Token::Bytes(b"abcd")],
+    );
+}
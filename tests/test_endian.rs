@@ -0,0 +1,118 @@
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{
+    assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, assert_tokens, Token,
+};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Test {
+    #[serde(with = "serde_bytes::le")]
+    le_u16: Vec<u16>,
+    #[serde(with = "serde_bytes::be")]
+    be_u32: [u32; 2],
+    #[serde(with = "serde_bytes::le")]
+    le_i64: Box<[i64]>,
+    #[serde(with = "serde_bytes::be")]
+    be_f32: Vec<f32>,
+    #[serde(with = "serde_bytes::le")]
+    le_f64: [f64; 1],
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+struct Borrowed<'a> {
+    #[serde(with = "serde_bytes::be")]
+    slice: &'a [i16],
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Samples(#[serde(with = "serde_bytes::le")] Vec<u32>);
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Pair(#[serde(with = "serde_bytes::be")] [u16; 2]);
+
+#[test]
+fn test_tokens() {
+    let test = Test {
+        le_u16: vec![1, 0x0203],
+        be_u32: [1, 0x0203_0405],
+        le_i64: vec![-2].into_boxed_slice(),
+        be_f32: vec![1.0],
+        le_f64: [-0.5],
+    };
+    assert_tokens(
+        &test,
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 5,
+            },
+            Token::Str("le_u16"),
+            Token::Bytes(&[1, 0, 3, 2]),
+            Token::Str("be_u32"),
+            Token::Bytes(&[0, 0, 0, 1, 2, 3, 4, 5]),
+            Token::Str("le_i64"),
+            Token::Bytes(&[0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+            Token::Str("be_f32"),
+            Token::Bytes(&[0x3f, 0x80, 0, 0]),
+            Token::Str("le_f64"),
+            Token::Bytes(&[0, 0, 0, 0, 0, 0, 0xe0, 0xbf]),
+            Token::StructEnd,
+        ],
+    );
+
+    assert_ser_tokens(
+        &Borrowed { slice: &[-1, 2] },
+        &[
+            Token::Struct {
+                name: "Borrowed",
+                len: 1,
+            },
+            Token::Str("slice"),
+            Token::Bytes(&[0xff, 0xff, 0, 2]),
+            Token::StructEnd,
+        ],
+    );
+
+    assert_de_tokens(
+        &Samples(vec![0x0403_0201]),
+        &[
+            Token::NewtypeStruct { name: "Samples" },
+            Token::Seq { len: Some(4) },
+            Token::U8(1),
+            Token::U8(2),
+            Token::U8(3),
+            Token::U8(4),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_length_errors() {
+    assert_de_tokens_error::<Samples>(
+        &[
+            Token::NewtypeStruct { name: "Samples" },
+            Token::Bytes(&[0; 6]),
+        ],
+        "invalid length 6, expected a byte string whose length is a multiple of 4",
+    );
+    assert_de_tokens_error::<Pair>(
+        &[Token::NewtypeStruct { name: "Pair" }, Token::Bytes(&[0; 6])],
+        "invalid length 6, expected a byte string of length 4",
+    );
+}
+
+#[test]
+fn test_bytes() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Signed(#[serde(with = "serde_bytes::le")] Vec<i8>);
+
+    assert_tokens(
+        &Signed(vec![-1, 2]),
+        &[
+            Token::NewtypeStruct { name: "Signed" },
+            Token::Bytes(&[0xff, 2]),
+        ],
+    );
+}
@@ -0,0 +1,94 @@
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde_bytes::adaptive::Encoding;
+use serde_bytes::escaped::{Backslash, Percent};
+use serde_bytes::ByteBuf;
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Readable, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Test {
+    #[serde(with = "serde_bytes::escaped")]
+    backslash: ByteBuf,
+    #[serde(with = "serde_bytes::escaped::percent")]
+    percent: Vec<u8>,
+}
+
+#[test]
+fn test_escaped() {
+    for (bytes, backslash, percent) in [
+        (&b""[..], "", ""),
+        (b"key", "key", "key"),
+        (b"a b\\c%d", "a b\\\\c%d", "a b\\c%25d"),
+        (b"\0\n\x7f\xff", "\\x00\\x0a\\x7f\\xff", "%00%0A%7F%FF"),
+    ] {
+        assert_eq!(Backslash::encode(bytes), backslash);
+        assert_eq!(Backslash::decode(backslash).unwrap(), bytes);
+        assert_eq!(Percent::encode(bytes), percent);
+        assert_eq!(Percent::decode(percent).unwrap(), bytes);
+    }
+
+    for malformed in [
+        "\\", "\\x0", "\\xg0", "\\n", "\\x41", "\\x5c", "\\xFF", "é", "\n",
+    ] {
+        assert!(Backslash::decode(malformed).is_err(), "{malformed:?}");
+    }
+    for malformed in ["%", "%0", "%G0", "%41", "%ff", "é", "\n"] {
+        assert!(Percent::decode(malformed).is_err(), "{malformed:?}");
+    }
+}
+
+#[test]
+fn test_tokens() {
+    let test = Test {
+        backslash: ByteBuf::from(b"k\x01".to_vec()),
+        percent: b"v\x02".to_vec(),
+    };
+    assert_tokens(
+        &test.clone().readable(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 2,
+            },
+            Token::Str("backslash"),
+            Token::Str("k\\x01"),
+            Token::Str("percent"),
+            Token::Str("v%02"),
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &test.compact(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 2,
+            },
+            Token::Str("backslash"),
+            Token::Bytes(b"k\x01"),
+            Token::Str("percent"),
+            Token::Bytes(b"v\x02"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_errors() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Key(#[serde(with = "serde_bytes::escaped")] Vec<u8>);
+
+    assert_de_tokens_error::<Readable<Key>>(
+        &[Token::NewtypeStruct { name: "Key" }, Token::Str("k\\x4")],
+        "invalid value: character `\\`, expected a valid backslash-escaped character at offset 1",
+    );
+    assert_de_tokens_error::<Readable<Key>>(
+        &[Token::NewtypeStruct { name: "Key" }, Token::Str("k\\x4g")],
+        "invalid value: character `g`, expected a valid backslash-escaped character at offset 4",
+    );
+    assert_de_tokens_error::<Readable<Key>>(
+        &[Token::NewtypeStruct { name: "Key" }, Token::Str("k\\x41")],
+        "invalid value: string \"k\\\\x41\", expected canonical backslash-escaped at offset 1",
+    );
+}
@@ -0,0 +1,111 @@
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde_bytes::adaptive::{Adaptive, Base64, Hex};
+use serde_bytes::lenient::Lenient;
+use serde_bytes::{ByteArray, ByteBuf};
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{
+    assert_de_tokens, assert_de_tokens_error, assert_tokens, Configure, Readable, Token,
+};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Test(#[serde(with = "serde_bytes::lenient")] ByteBuf);
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct Custom(#[serde(with = "Lenient::<Adaptive<Base64>, (Base64, Hex)>")] Option<ByteArray<2>>);
+
+#[test]
+fn test_accepted() {
+    let test = Test(ByteBuf::from(vec![0xca, 0xfe]));
+    for token in [
+        Token::Bytes(&[0xca, 0xfe]),
+        Token::BorrowedBytes(&[0xca, 0xfe]),
+        Token::ByteBuf(&[0xca, 0xfe]),
+        Token::Str("cafe"),
+        Token::Str("CAFE"),
+        Token::Str("0xcafe"),
+        Token::Str("base64:yv4="),
+        Token::Str("base64url:yv4"),
+        Token::Str("yv4="),
+    ] {
+        assert_de_tokens(
+            &test.clone().readable(),
+            &[Token::NewtypeStruct { name: "Test" }, token],
+        );
+    }
+    assert_de_tokens(
+        &test.readable(),
+        &[
+            Token::NewtypeStruct { name: "Test" },
+            Token::Seq { len: Some(2) },
+            Token::U8(0xca),
+            Token::U8(0xfe),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_canonical() {
+    let test = Test(ByteBuf::from(vec![0xca, 0xfe]));
+    assert_tokens(
+        &test.clone().readable(),
+        &[Token::NewtypeStruct { name: "Test" }, Token::Str("cafe")],
+    );
+    assert_tokens(
+        &test.compact(),
+        &[
+            Token::NewtypeStruct { name: "Test" },
+            Token::Bytes(&[0xca, 0xfe]),
+        ],
+    );
+
+    let custom = Custom(Some(ByteArray::new([0xca, 0xfe])));
+    assert_tokens(
+        &custom.clone().readable(),
+        &[
+            Token::NewtypeStruct { name: "Custom" },
+            Token::Some,
+            Token::Str("yv4="),
+        ],
+    );
+    assert_de_tokens(
+        &custom.readable(),
+        &[
+            Token::NewtypeStruct { name: "Custom" },
+            Token::Some,
+            Token::Str("0xcafe"),
+        ],
+    );
+}
+
+#[test]
+fn test_errors() {
+    assert_de_tokens_error::<Readable<Test>>(
+        &[Token::NewtypeStruct { name: "Test" }, Token::Str("0xyv4=")],
+        "invalid value: character `y`, expected a valid hex character at offset 2",
+    );
+    assert_de_tokens_error::<Readable<Test>>(
+        &[
+            Token::NewtypeStruct { name: "Test" },
+            Token::Str("base64:yv4"),
+        ],
+        "invalid length 3, expected a base64 string whose length is a multiple of 4",
+    );
+    assert_de_tokens_error::<Readable<Test>>(
+        &[Token::NewtypeStruct { name: "Test" }, Token::Str("yv4!")],
+        "invalid value: string \"yv4!\", expected a byte string, a sequence of u8, or a hex, base64 or base64url string",
+    );
+    assert_de_tokens_error::<Readable<Custom>>(
+        &[
+            Token::NewtypeStruct { name: "Custom" },
+            Token::Some,
+            Token::Str("!"),
+        ],
+        "invalid value: string \"!\", expected a byte string, a sequence of u8, or a base64 or hex string",
+    );
+    assert_de_tokens_error::<Readable<Test>>(
+        &[Token::NewtypeStruct { name: "Test" }, Token::U8(1)],
+        "invalid type: integer `1`, expected a byte string, a sequence of u8, or a hex, base64 or base64url string",
+    );
+}
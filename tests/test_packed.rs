@@ -0,0 +1,88 @@
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde_bytes::ByteArray;
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Test {
+    #[serde(with = "serde_bytes::packed")]
+    vec: Vec<u8>,
+    #[serde(with = "serde_bytes::packed")]
+    array: ByteArray<9>,
+    #[serde(with = "serde_bytes::packed")]
+    opt: Option<Vec<u8>>,
+}
+
+#[test]
+fn test_json() {
+    let test = Test {
+        vec: vec![1, 2, 3],
+        array: ByteArray::new([1, 2, 3, 4, 5, 6, 7, 8, 9]),
+        opt: None,
+    };
+    let json = serde_json::to_string(&test).unwrap();
+    assert_eq!(
+        json,
+        r#"{"vec":{"len":3,"data":[72623842526232576]},"array":{"len":9,"data":[72623859790382856,648518346341351424]},"opt":null}"#,
+    );
+    assert_eq!(serde_json::from_str::<Test>(&json).unwrap(), test);
+
+    let json = r#"{"vec":{"len":18446744073709551615,"data":[]},"array":{"len":9,"data":[0,0]},"opt":null}"#;
+    assert_eq!(
+        serde_json::from_str::<Test>(json).unwrap_err().to_string(),
+        "invalid length 0, expected one word per 8 bytes of `len` at line 1 column 45",
+    );
+}
+
+#[test]
+fn test_tokens() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Wrapper(#[serde(with = "serde_bytes::packed")] Option<Vec<u8>>);
+
+    assert_tokens(
+        &Wrapper(Some(vec![])),
+        &[
+            Token::NewtypeStruct { name: "Wrapper" },
+            Token::Some,
+            Token::Struct {
+                name: "PackedBytes",
+                len: 2,
+            },
+            Token::Str("len"),
+            Token::U64(0),
+            Token::Str("data"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    assert_de_tokens(
+        &Wrapper(Some(b"ABC".to_vec())),
+        &[
+            Token::NewtypeStruct { name: "Wrapper" },
+            Token::Some,
+            Token::Bytes(b"ABC"),
+        ],
+    );
+
+    assert_de_tokens_error::<Wrapper>(
+        &[
+            Token::NewtypeStruct { name: "Wrapper" },
+            Token::Some,
+            Token::Struct {
+                name: "PackedBytes",
+                len: 2,
+            },
+            Token::Str("len"),
+            Token::U64(9),
+            Token::Str("data"),
+            Token::Seq { len: Some(1) },
+            Token::U64(0),
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+        "invalid length 1, expected one word per 8 bytes of `len`",
+    );
+}
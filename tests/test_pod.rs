@@ -0,0 +1,65 @@
+#![cfg(feature = "bytemuck")]
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde::de::value::{BorrowedBytesDeserializer, BytesDeserializer, Error};
+use serde::Deserialize;
+use serde_bytes::{PodCow, PodSlice};
+use serde_test::{assert_de_tokens_error, assert_ser_tokens, Token};
+
+#[repr(C, align(4))]
+struct Aligned([u8; 9]);
+
+static DATA: Aligned = Aligned([1, 0, 0, 0, 2, 0, 0, 0, 3]);
+
+fn borrowed<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> T {
+    T::deserialize(BorrowedBytesDeserializer::<Error>::new(bytes)).unwrap()
+}
+
+fn transient<'de, T: Deserialize<'de>>(bytes: &[u8]) -> T {
+    T::deserialize(BytesDeserializer::<Error>::new(bytes)).unwrap()
+}
+
+fn ne(bytes: [u8; 4]) -> u32 {
+    u32::from_ne_bytes(bytes)
+}
+
+#[test]
+fn test_pod_slice() {
+    let slice: PodSlice<u32> = borrowed(&DATA.0[..8]);
+    assert_eq!(*slice, [ne([1, 0, 0, 0]), ne([2, 0, 0, 0])]);
+    assert_ser_tokens(&slice, &[Token::Bytes(&DATA.0[..8])]);
+
+    assert_de_tokens_error::<PodSlice<u32>>(
+        &[Token::BorrowedBytes(&DATA.0[1..5])],
+        "borrowed bytes are not aligned to 4 bytes; use PodCow to copy them instead",
+    );
+    assert_de_tokens_error::<PodSlice<u32>>(
+        &[Token::BorrowedBytes(&DATA.0[..6])],
+        "invalid length 6, expected a byte string whose length is a multiple of 4",
+    );
+    assert_de_tokens_error::<PodSlice<u32>>(
+        &[Token::Bytes(&DATA.0[..8])],
+        "invalid type: byte array, expected a borrowed byte array",
+    );
+}
+
+#[test]
+fn test_pod_cow() {
+    let aligned: PodCow<u32> = borrowed(&DATA.0[..8]);
+    assert!(aligned.is_borrowed());
+    assert_eq!(*aligned, [ne([1, 0, 0, 0]), ne([2, 0, 0, 0])]);
+
+    let misaligned: PodCow<u32> = borrowed(&DATA.0[1..9]);
+    assert!(!misaligned.is_borrowed());
+    assert_eq!(*misaligned, [ne([0, 0, 0, 2]), ne([0, 0, 0, 3])]);
+
+    let owned: PodCow<u32> = transient(&DATA.0[..4]);
+    assert!(!owned.is_borrowed());
+    assert_eq!(*owned, [ne([1, 0, 0, 0])]);
+    assert_ser_tokens(&owned, &[Token::Bytes(&DATA.0[..4])]);
+
+    assert_de_tokens_error::<PodCow<u32>>(
+        &[Token::Bytes(&DATA.0[..5])],
+        "invalid length 5, expected a byte string whose length is a multiple of 4",
+    );
+}
@@ -1,5 +1,8 @@
-use serde_bytes::{ByteArray, ByteBuf, Bytes};
-use serde_test::{assert_de_tokens, assert_ser_tokens, assert_tokens, Token};
+use serde_bytes::{ByteArray, ByteBuf, Bytes, Codec, Embedded, MsgPackExt, Tagged};
+use serde_test::{
+    assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, assert_tokens, Compact, Configure,
+    Token,
+};
 
 #[test]
 fn test_bytes() {
@@ -73,3 +76,114 @@ fn test_bytearray() {
     assert_ser_tokens(&bytes, &[Token::ByteBuf(b"ABC")]);
     assert_de_tokens(&bytes, &[Token::BorrowedStr("ABC")]);
 }
+
+#[test]
+fn test_msgpack_ext() {
+    let ext = MsgPackExt::<5, ByteBuf>(ByteBuf::from(vec![65, 66, 67]));
+    assert_tokens(
+        &ext,
+        &[
+            Token::NewtypeStruct { name: "_ExtStruct" },
+            Token::Tuple { len: 2 },
+            Token::I8(5),
+            Token::Bytes(b"ABC"),
+            Token::TupleEnd,
+        ],
+    );
+
+    let borrowed = MsgPackExt::<5, &Bytes>(Bytes::new(b"ABC"));
+    assert_tokens(
+        &borrowed,
+        &[
+            Token::NewtypeStruct { name: "_ExtStruct" },
+            Token::Tuple { len: 2 },
+            Token::I8(5),
+            Token::BorrowedBytes(b"ABC"),
+            Token::TupleEnd,
+        ],
+    );
+
+    assert_de_tokens_error::<MsgPackExt<5, ByteBuf>>(
+        &[
+            Token::NewtypeStruct { name: "_ExtStruct" },
+            Token::Tuple { len: 2 },
+            Token::I8(6),
+        ],
+        "invalid value: integer `6`, expected MessagePack extension type 5",
+    );
+}
+
+#[test]
+fn test_cbor_tagged() {
+    let tagged = Tagged::<24, ByteBuf>(ByteBuf::from(vec![65, 66, 67]));
+    assert_tokens(
+        &tagged.clone().compact(),
+        &[
+            Token::TupleVariant {
+                name: "@@TAG@@",
+                variant: "@@TAGGED@@",
+                len: 2,
+            },
+            Token::U64(24),
+            Token::Bytes(b"ABC"),
+            Token::TupleVariantEnd,
+        ],
+    );
+    assert_tokens(&tagged.readable(), &[Token::Bytes(b"ABC")]);
+
+    assert_de_tokens_error::<Compact<Tagged<24, ByteBuf>>>(
+        &[
+            Token::TupleVariant {
+                name: "@@TAG@@",
+                variant: "@@TAGGED@@",
+                len: 2,
+            },
+            Token::U64(25),
+        ],
+        "invalid value: integer `25`, expected byte string with CBOR tag 24",
+    );
+    assert_de_tokens_error::<Compact<Tagged<24, ByteBuf>>>(
+        &[Token::NewtypeVariant {
+            name: "@@TAG@@",
+            variant: "@@UNTAGGED@@",
+        }],
+        "invalid value: untagged value, expected byte string with CBOR tag 24",
+    );
+}
+
+struct BigEndian;
+
+impl Codec<u32> for BigEndian {
+    type Error = &'static str;
+
+    fn encode(value: &u32) -> Result<ByteBuf, Self::Error> {
+        Ok(ByteBuf::from(value.to_be_bytes()))
+    }
+
+    fn decode(bytes: &[u8]) -> Result<u32, Self::Error> {
+        let array = bytes.try_into().map_err(|_| "expected 4 bytes")?;
+        Ok(u32::from_be_bytes(array))
+    }
+}
+
+#[test]
+fn test_embedded() {
+    let embedded = Embedded::<u32, BigEndian>::encode(&0x4142_4344).unwrap();
+    assert_eq!(embedded.raw(), b"ABCD");
+    assert_eq!(embedded.decode(), Ok(0x4142_4344));
+    assert_tokens(&embedded, &[Token::Bytes(b"ABCD")]);
+    assert_de_tokens(
+        &embedded,
+        &[
+            Token::Seq { len: Some(4) },
+            Token::U8(65),
+            Token::U8(66),
+            Token::U8(67),
+            Token::U8(68),
+            Token::SeqEnd,
+        ],
+    );
+
+    let truncated = Embedded::<u32, BigEndian>::from_raw(ByteBuf::from(b"ABC".to_vec()));
+    assert_eq!(truncated.decode(), Err("expected 4 bytes"));
+}